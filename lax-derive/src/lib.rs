@@ -1,25 +1,114 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::Attribute;
-use syn::{parse, parse_macro_input, ItemStruct};
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::{parse, parse_macro_input, Attribute, Expr, ItemStruct, Lit, Meta, Token, Type};
 
-/// Add attribute #[serde(default)] for each fields of a struct.
+/// Give a default value to each mandatory field of a struct, applied by the `lax` feature of `tef`.
+///
+/// A missing field is set to `Default::default()` and recorded by `crate::de::defaulted`, so the strict parser
+/// still rejects it. The lenient parser doesn't rely on it, it fills the missing fields itself. Optional fields
+/// and fields that already have a default are left untouched.
 #[proc_macro_attribute]
 pub fn lax(args: TokenStream, input: TokenStream) -> TokenStream {
     let mut item_struct = parse_macro_input!(input as ItemStruct);
     let _ = parse_macro_input!(args as parse::Nothing);
 
+    let ident = item_struct.ident.clone();
+    let mut defaults = vec![];
+
     if let syn::Fields::Named(ref mut fields) = item_struct.fields {
         for field in &mut fields.named {
+            let serde_args = serde_args(&field.attrs);
+
+            if is_option(&field.ty) || has_default(&serde_args) {
+                continue;
+            }
+
+            let field_ident = field.ident.clone().expect("named field");
+            let name = rename(&serde_args)
+                .unwrap_or_else(|| field_ident.to_string().trim_start_matches("r#").to_string());
+            let function = format_ident!("__lax_default_{}", field_ident.to_string().trim_start_matches("r#"));
+            let path = format!("{}::{}", ident, function);
+            let ty = &field.ty;
+
             let attr: Attribute = syn::parse_quote! {
-                #[serde(default)]
+                #[serde(default = #path)]
             };
             field.attrs.push(attr);
+
+            defaults.push(quote! {
+                #[doc(hidden)]
+                fn #function() -> #ty {
+                    crate::de::defaulted(#name)
+                }
+            });
         }
     }
 
-    return quote!(
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+
+    quote!(
         #item_struct
-    ).into();
-}
\ No newline at end of file
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#defaults)*
+        }
+    )
+    .into()
+}
+
+/// Arguments of the `#[serde(...)]` attributes of a field
+fn serde_args(attrs: &[Attribute]) -> Vec<Meta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+fn has_default(args: &[Meta]) -> bool {
+    args.iter().any(|meta| meta.path().is_ident("default"))
+}
+
+/// Name of the field in the document, `rename = "..."` or `rename(deserialize = "...")`
+fn rename(args: &[Meta]) -> Option<String> {
+    args.iter().find_map(|meta| match meta {
+        Meta::NameValue(meta) if meta.path.is_ident("rename") => string(&meta.value),
+        Meta::List(list) if list.path.is_ident("rename") => list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .ok()?
+            .into_iter()
+            .find_map(|meta| match meta {
+                Meta::NameValue(meta) if meta.path.is_ident("deserialize") => string(&meta.value),
+                _ => None,
+            }),
+        _ => None,
+    })
+}
+
+fn string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Str(lit) => Some(lit.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
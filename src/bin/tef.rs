@@ -0,0 +1,683 @@
+//! Command-line tool to validate, query and convert TEF records
+//!
+//! Inputs are files, directories (searched recursively for `.xml` files) or `-` for the standard input, which is
+//! also read when no input is given. The standard input is read once, however many times `-` is given.
+//!
+//! Exit codes: 0 on success, 1 when a record is invalid, 2 on a usage error, 3 when an input can't be read or
+//! parsed.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use tef::mets::Mets;
+use tef::tef::Person;
+use tef::validation::Severity;
+use tef::{
+    parse_tef_from_slice_with, to_bibtex, to_csl_json_string, to_json_ld_string, to_marcxml_string,
+    to_ntriples, to_oai_dc_string, to_ris, to_tef_string, to_turtle, to_unimarc, validate, Nnt,
+    ParseOptions, Thesis,
+};
+
+const INVALID: u8 = 1;
+const UNREADABLE: u8 = 3;
+
+#[derive(Parser)]
+#[command(
+    name = "tef",
+    version,
+    about = "Validate, query and convert TEF records"
+)]
+struct Cli {
+    /// Fill missing mandatory elements with their default value instead of failing
+    #[arg(long, global = true)]
+    lenient: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check the records against the rules of the TEF profile
+    Validate {
+        /// Only print errors, not warnings
+        #[arg(short, long)]
+        quiet: bool,
+        inputs: Vec<PathBuf>,
+    },
+    /// Print a field of each record, one value per line
+    Extract { field: Field, inputs: Vec<PathBuf> },
+    /// Convert each record to another format
+    Convert {
+        #[arg(long)]
+        to: Format,
+        /// Write each record to this directory instead of the standard output
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        inputs: Vec<PathBuf>,
+    },
+    /// Print an overview of each record
+    Summary { inputs: Vec<PathBuf> },
+    /// Print statistics over all the records
+    Stats { inputs: Vec<PathBuf> },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Field {
+    Nnt,
+    Title,
+    Author,
+    Persons,
+    Date,
+    Discipline,
+    Grantor,
+    Language,
+    Subject,
+    Abstract,
+    SetSpec,
+    Files,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// TEF, as written by this crate
+    Tef,
+    /// JSON view of the model
+    Json,
+    /// Simple Dublin Core, as exposed over OAI-PMH
+    OaiDc,
+    /// UNIMARC in ISO 2709, records are written one after the other
+    Unimarc,
+    /// UNIMARC in MARCXML
+    Marcxml,
+    /// schema.org Thesis in JSON-LD
+    Jsonld,
+    /// RDF in N-Triples
+    Ntriples,
+    /// RDF in Turtle
+    Turtle,
+    /// BibTeX @phdthesis entry
+    Bibtex,
+    /// RIS THES record
+    Ris,
+    /// CSL-JSON thesis item
+    CslJson,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Tef => "xml",
+            Format::Json => "json",
+            Format::OaiDc => "oai_dc.xml",
+            Format::Unimarc => "mrc",
+            Format::Marcxml => "marc.xml",
+            Format::Jsonld => "jsonld",
+            Format::Ntriples => "nt",
+            Format::Turtle => "ttl",
+            Format::Bibtex => "bib",
+            Format::Ris => "ris",
+            Format::CslJson => "csl.json",
+        }
+    }
+
+    fn convert(&self, mets: &Mets) -> Result<String, String> {
+        match self {
+            Format::Tef => to_tef_string(mets).map_err(|err| err.to_string()),
+            Format::Json => serde_json::to_string_pretty(mets).map_err(|err| err.to_string()),
+            Format::OaiDc => to_oai_dc_string(mets).map_err(|err| err.to_string()),
+            Format::Unimarc => to_unimarc(mets)
+                .map_err(|err| err.to_string())
+                .and_then(|record| String::from_utf8(record).map_err(|err| err.to_string())),
+            Format::Marcxml => to_marcxml_string(mets).map_err(|err| err.to_string()),
+            Format::Jsonld => to_json_ld_string(mets).map_err(|err| err.to_string()),
+            Format::Ntriples => to_ntriples(mets).map_err(|err| err.to_string()),
+            Format::Turtle => to_turtle(mets).map_err(|err| err.to_string()),
+            Format::Bibtex => to_bibtex(mets).map_err(|err| err.to_string()),
+            Format::Ris => to_ris(mets).map_err(|err| err.to_string()),
+            Format::CslJson => to_csl_json_string(mets).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Record read from an input
+struct Input {
+    name: String,
+    /// Path of the input relative to the directory it was found in, or its file name
+    relative: PathBuf,
+    result: Result<Mets, String>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let options = match cli.lenient {
+        true => ParseOptions::lenient(),
+        false => ParseOptions::default(),
+    };
+
+    let result = match cli.command {
+        Command::Validate { quiet, inputs } => run_validate(&Inputs::new(inputs, options), quiet),
+        Command::Extract { field, inputs } => run_extract(&Inputs::new(inputs, options), field),
+        Command::Convert { to, output, inputs } => {
+            run_convert(&Inputs::new(inputs, options), to, output.as_deref())
+        }
+        Command::Summary { inputs } => run_summary(&Inputs::new(inputs, options)),
+        Command::Stats { inputs } => run_stats(&Inputs::new(inputs, options)),
+    };
+
+    match result {
+        Ok(code) => ExitCode::from(code),
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("tef: {}", err);
+            ExitCode::from(UNREADABLE)
+        }
+    }
+}
+
+/// Inputs, directories expanded, parsed one at a time
+struct Inputs {
+    /// Path of each input and its path relative to the directory it was found in
+    paths: Vec<(PathBuf, PathBuf)>,
+    options: ParseOptions,
+}
+
+impl Inputs {
+    fn new(inputs: Vec<PathBuf>, options: ParseOptions) -> Self {
+        let inputs = match inputs.is_empty() {
+            true => vec![PathBuf::from("-")],
+            false => inputs,
+        };
+
+        let mut paths: Vec<(PathBuf, PathBuf)> = vec![];
+        for input in inputs {
+            if input.is_dir() {
+                collect(&input, &input, &mut paths);
+            } else if input.as_os_str() != "-" || !paths.iter().any(|(path, _)| path == &input) {
+                let relative = PathBuf::from(input.file_name().unwrap_or(input.as_os_str()));
+                paths.push((input, relative));
+            }
+        }
+
+        Self { paths, options }
+    }
+
+    fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Input> + '_ {
+        self.paths.iter().map(|(path, relative)| {
+            let (name, bytes) = match path.as_os_str() == "-" {
+                true => {
+                    let mut bytes = vec![];
+                    let result = io::stdin().read_to_end(&mut bytes).map(|_| bytes);
+                    ("-".to_string(), result)
+                }
+                false => (path.display().to_string(), fs::read(path)),
+            };
+
+            let result = bytes.map_err(|err| err.to_string()).and_then(|bytes| {
+                parse_tef_from_slice_with(&bytes, &self.options)
+                    .map(|(mets, _)| mets)
+                    .map_err(|err| err.to_string())
+            });
+
+            Input {
+                name,
+                relative: relative.clone(),
+                result,
+            }
+        })
+    }
+}
+
+/// `.xml` files of a directory and its subdirectories, sorted by path, with their path relative to `root`
+fn collect(root: &Path, dir: &Path, paths: &mut Vec<(PathBuf, PathBuf)>) {
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect(),
+        Err(_) => {
+            let name = PathBuf::from(dir.file_name().unwrap_or(dir.as_os_str()));
+            return paths.push((dir.to_path_buf(), name));
+        }
+    };
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect(root, &entry, paths);
+        } else if entry
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("xml"))
+        {
+            let relative = relative(&entry);
+            paths.push((entry, relative));
+        }
+    }
+}
+
+/// Print the error of an input that can't be read and keep the worst exit code
+fn failed(input: &Input, err: &str, code: &mut u8) {
+    eprintln!("{}: {}", input.name, err);
+    *code = UNREADABLE;
+}
+
+fn run_validate(inputs: &Inputs, quiet: bool) -> io::Result<u8> {
+    let mut out = io::stdout().lock();
+    let mut code = 0;
+
+    for input in inputs.iter() {
+        let mets = match &input.result {
+            Ok(mets) => mets,
+            Err(err) => {
+                failed(&input, err, &mut code);
+                continue;
+            }
+        };
+
+        let report = validate(mets);
+        for violation in report.violations() {
+            if !quiet || violation.severity() == &Severity::Error {
+                writeln!(out, "{}: {}", input.name, violation)?;
+            }
+        }
+        if !report.is_valid() {
+            code = code.max(INVALID);
+        }
+    }
+
+    Ok(code)
+}
+
+fn run_extract(inputs: &Inputs, field: Field) -> io::Result<u8> {
+    let mut out = io::stdout().lock();
+    let mut code = 0;
+
+    for input in inputs.iter() {
+        let mets = match &input.result {
+            Ok(mets) => mets,
+            Err(err) => {
+                failed(&input, err, &mut code);
+                continue;
+            }
+        };
+
+        for value in extract(mets, field) {
+            match inputs.len() {
+                1 => writeln!(out, "{}", value)?,
+                _ => writeln!(out, "{}\t{}", input.name, value)?,
+            }
+        }
+    }
+
+    Ok(code)
+}
+
+fn extract(mets: &Mets, field: Field) -> Vec<String> {
+    let record = mets.thesis_record();
+    let admin = mets.thesis_admin();
+
+    match field {
+        Field::Nnt => Nnt::from_mets(mets)
+            .map(|nnt| nnt.to_string())
+            .into_iter()
+            .collect(),
+        Field::Title => record
+            .map(|record| record.title().text().trim().to_string())
+            .into_iter()
+            .collect(),
+        Field::Author => admin
+            .map(|admin| admin.auteur().display_name())
+            .into_iter()
+            .collect(),
+        Field::Persons => admin
+            .map(|admin| {
+                admin
+                    .persons()
+                    .into_iter()
+                    .map(|(role, person)| format!("{}\t{}", role, person.display_name()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Field::Date => admin
+            .map(|admin| admin.date_accepted().text().trim().to_string())
+            .into_iter()
+            .collect(),
+        Field::Discipline => admin
+            .map(|admin| admin.thesis_degree().discipline().text().trim().to_string())
+            .into_iter()
+            .collect(),
+        Field::Grantor => admin
+            .map(|admin| {
+                admin
+                    .thesis_degree()
+                    .grantor()
+                    .iter()
+                    .map(|grantor| grantor.nom().0.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Field::Language => record
+            .map(|record| {
+                record
+                    .language()
+                    .iter()
+                    .map(|language| language.text().trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Field::Subject => record
+            .map(|record| {
+                record
+                    .subject()
+                    .iter()
+                    .flatten()
+                    .map(|subject| subject.text().trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Field::Abstract => record
+            .map(|record| {
+                record
+                    .r#abstract()
+                    .iter()
+                    .flatten()
+                    .map(|r#abstract| {
+                        format!("{}\t{}", r#abstract.lang(), one_line(r#abstract.text()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Field::SetSpec => admin
+            .map(|admin| {
+                admin
+                    .oai_set_spec()
+                    .iter()
+                    .map(|set_spec| set_spec.0.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Field::Files => match Thesis::from_mets(mets) {
+            Ok(thesis) => thesis
+                .editions()
+                .flat_map(|edition| edition.metadata().files().clone())
+                .map(|file| file.value().href().trim().to_string())
+                .collect(),
+            Err(_) => vec![],
+        },
+    }
+}
+
+fn run_convert(inputs: &Inputs, format: Format, output: Option<&Path>) -> io::Result<u8> {
+    let mut out = io::stdout().lock();
+    let mut code = 0;
+    let mut written: HashSet<PathBuf> = HashSet::new();
+
+    if let Some(output) = output {
+        fs::create_dir_all(output)?;
+    }
+
+    for input in inputs.iter() {
+        let converted = input
+            .result
+            .as_ref()
+            .map_err(String::clone)
+            .and_then(|mets| format.convert(mets));
+        let converted = match converted {
+            Ok(converted) => converted,
+            Err(err) => {
+                failed(&input, &err, &mut code);
+                continue;
+            }
+        };
+
+        match output {
+            Some(output) => {
+                // the tree of the input directories is kept, records never overwrite each other
+                let relative = match input.relative.as_os_str() == "-" {
+                    true => Path::new("stdin"),
+                    false => input.relative.as_path(),
+                };
+                let path = output.join(relative).with_extension(format.extension());
+                if !written.insert(path.clone()) {
+                    let err = format!("{} is already written by another input", path.display());
+                    failed(&input, &err, &mut code);
+                    continue;
+                }
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, converted)?;
+            }
+            // ISO 2709 records carry their own terminator
+            None if matches!(format, Format::Unimarc) => write!(out, "{}", converted)?,
+            None => writeln!(out, "{}", converted.trim_end())?,
+        }
+    }
+
+    Ok(code)
+}
+
+fn run_summary(inputs: &Inputs) -> io::Result<u8> {
+    let mut out = io::stdout().lock();
+    let mut code = 0;
+
+    for (index, input) in inputs.iter().enumerate() {
+        let mets = match &input.result {
+            Ok(mets) => mets,
+            Err(err) => {
+                failed(&input, err, &mut code);
+                continue;
+            }
+        };
+
+        if index > 0 {
+            writeln!(out)?;
+        }
+        if inputs.len() > 1 {
+            writeln!(out, "{}", input.name)?;
+        }
+        write!(out, "{}", summary(mets))?;
+    }
+
+    Ok(code)
+}
+
+fn summary(mets: &Mets) -> String {
+    let record = mets.thesis_record();
+    let admin = mets.thesis_admin();
+    let mut lines: Vec<(&str, String)> = vec![];
+
+    lines.push((
+        "NNT",
+        Nnt::from_mets(mets)
+            .map(|nnt| nnt.to_string())
+            .unwrap_or_default(),
+    ));
+    if let Some(record) = record {
+        lines.push(("Title", record.title().text().trim().to_string()));
+    }
+    if let Some(admin) = admin {
+        let degree = admin.thesis_degree();
+        lines.push(("Author", admin.auteur().display_name()));
+        lines.push(("Defense", admin.date_accepted().text().trim().to_string()));
+        lines.push(("Degree", degree.level().text().as_str().to_string()));
+        lines.push(("Discipline", degree.discipline().text().trim().to_string()));
+        lines.push((
+            "Grantor",
+            join(
+                degree
+                    .grantor()
+                    .iter()
+                    .map(|grantor| grantor.nom().0.trim()),
+            ),
+        ));
+        lines.push((
+            "Directors",
+            join(
+                admin
+                    .directeur_these()
+                    .iter()
+                    .map(|person| person.display_name()),
+            ),
+        ));
+        lines.push((
+            "Jury",
+            join(
+                admin
+                    .president_jury()
+                    .iter()
+                    .map(|person| person.display_name())
+                    .chain(
+                        admin
+                            .membre_jury()
+                            .iter()
+                            .map(|person| person.display_name()),
+                    ),
+            ),
+        ));
+        lines.push((
+            "Doctoral school",
+            join(
+                admin
+                    .ecole_doctorale()
+                    .iter()
+                    .map(|ecole| ecole.nom().0.trim()),
+            ),
+        ));
+    }
+    if let Some(record) = record {
+        lines.push((
+            "Languages",
+            join(
+                record
+                    .language()
+                    .iter()
+                    .map(|language| language.text().trim()),
+            ),
+        ));
+    }
+    if let Ok(thesis) = Thesis::from_mets(mets) {
+        lines.push(("Versions", thesis.versions().len().to_string()));
+        lines.push(("Editions", thesis.editions().count().to_string()));
+        lines.push((
+            "Files",
+            thesis
+                .editions()
+                .map(|edition| edition.metadata().files().len())
+                .sum::<usize>()
+                .to_string(),
+        ));
+    }
+
+    let report = validate(mets);
+    lines.push((
+        "Validation",
+        format!(
+            "{} errors, {} warnings",
+            report.errors().count(),
+            report.warnings().count()
+        ),
+    ));
+
+    lines
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(label, value)| format!("{:<18}{}\n", format!("{}:", label), value))
+        .collect()
+}
+
+fn run_stats(inputs: &Inputs) -> io::Result<u8> {
+    let mut out = io::stdout().lock();
+    let mut code = 0;
+    let (mut parsed, mut valid, mut warned) = (0, 0, 0);
+    let mut years: BTreeMap<String, usize> = BTreeMap::new();
+    let mut grantors: BTreeMap<String, usize> = BTreeMap::new();
+    let mut disciplines: BTreeMap<String, usize> = BTreeMap::new();
+    let mut languages: BTreeMap<String, usize> = BTreeMap::new();
+
+    for input in inputs.iter() {
+        let mets = match &input.result {
+            Ok(mets) => mets,
+            Err(err) => {
+                failed(&input, err, &mut code);
+                continue;
+            }
+        };
+
+        parsed += 1;
+        let report = validate(mets);
+        if report.is_valid() {
+            valid += 1;
+        }
+        if report.warnings().next().is_some() {
+            warned += 1;
+        }
+
+        if let Some(admin) = mets.thesis_admin() {
+            let year = match admin.date_accepted().date() {
+                Ok(date) => date.year().to_string(),
+                Err(_) => "unknown".to_string(),
+            };
+            *years.entry(year).or_default() += 1;
+            for grantor in admin.thesis_degree().grantor() {
+                *grantors
+                    .entry(grantor.nom().0.trim().to_string())
+                    .or_default() += 1;
+            }
+            let discipline = admin.thesis_degree().discipline().text().trim().to_string();
+            *disciplines.entry(discipline).or_default() += 1;
+        }
+        if let Some(record) = mets.thesis_record() {
+            for language in record.language() {
+                *languages
+                    .entry(language.text().trim().to_string())
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    writeln!(out, "records\t{}", inputs.len())?;
+    writeln!(out, "parsed\t{}", parsed)?;
+    writeln!(out, "unreadable\t{}", inputs.len() - parsed)?;
+    writeln!(out, "valid\t{}", valid)?;
+    writeln!(out, "invalid\t{}", parsed - valid)?;
+    writeln!(out, "with warnings\t{}", warned)?;
+
+    for (title, counts) in [
+        ("year", years),
+        ("grantor", grantors),
+        ("discipline", disciplines),
+        ("language", languages),
+    ] {
+        let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+        writeln!(out, "\nby {}", title)?;
+        for (value, count) in counts {
+            writeln!(out, "{}\t{}", value, count)?;
+        }
+    }
+
+    Ok(code)
+}
+
+fn join<T: AsRef<str>>(values: impl Iterator<Item = T>) -> String {
+    values
+        .map(|value| value.as_ref().to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Text on a single line, for the tab separated output
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
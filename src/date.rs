@@ -0,0 +1,312 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// `xsi:type` of the dates encoded in W3CDTF
+pub const W3CDTF: &str = "dcterms:W3CDTF";
+
+/// Precision of a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DatePrecision {
+    Year,
+    Month,
+    Day,
+}
+
+/// Date of a TEF record
+///
+/// Parsed from W3CDTF / ISO 8601 (`2019`, `2019-12`, `2019-12-01`, `2019-12-01T10:00:00Z`...) or from the
+/// `dd/mm/yyyy` form of the STAR and STEP exports. The time of day, when present, is checked but not kept.
+///
+/// Dates are ordered chronologically, a date sorts before the more precise dates of the same period, e.g.
+/// `2019 < 2019-01 < 2019-01-01`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TefDate {
+    year: u16,
+    month: Option<u8>,
+    day: Option<u8>,
+}
+
+impl TefDate {
+    /// Year only date
+    pub fn year_only(year: u16) -> Self {
+        Self {
+            year,
+            month: None,
+            day: None,
+        }
+    }
+
+    /// Date with a month, the month must be in `1..=12`
+    pub fn year_month(year: u16, month: u8) -> Result<Self, Error> {
+        if !(1..=12).contains(&month) {
+            return Err(invalid(
+                format!("{year:04}-{month:02}"),
+                "month out of range",
+            ));
+        }
+
+        Ok(Self {
+            year,
+            month: Some(month),
+            day: None,
+        })
+    }
+
+    /// Complete date, the day must exist in the month
+    pub fn ymd(year: u16, month: u8, day: u8) -> Result<Self, Error> {
+        let date = Self::year_month(year, month)?;
+
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(invalid(
+                format!("{year:04}-{month:02}-{day:02}"),
+                "day out of range",
+            ));
+        }
+
+        Ok(Self {
+            day: Some(day),
+            ..date
+        })
+    }
+
+    /// Parse a W3CDTF / ISO 8601 date, see [`TefDate`]
+    pub fn parse_w3cdtf(value: &str) -> Result<Self, Error> {
+        let value = value.trim();
+        let (date, time) = match value.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (value, None),
+        };
+
+        let parts: Vec<&str> = date.split('-').collect();
+        let date = match parts.as_slice() {
+            [year] => Self::year_only(number(value, year, 4)?),
+            [year, month] => Self::year_month(number(value, year, 4)?, number(value, month, 2)?)?,
+            [year, month, day] => Self::ymd(
+                number(value, year, 4)?,
+                number(value, month, 2)?,
+                number(value, day, 2)?,
+            )?,
+            _ => return Err(invalid(value, "expected YYYY, YYYY-MM or YYYY-MM-DD")),
+        };
+
+        if let Some(time) = time {
+            if date.precision() != DatePrecision::Day {
+                return Err(invalid(value, "a time requires a complete date"));
+            }
+            check_time(value, time)?;
+        }
+
+        Ok(date)
+    }
+
+    /// Parse a `dd/mm/yyyy` date, optionally followed by a time separated by a space
+    pub fn parse_french(value: &str) -> Result<Self, Error> {
+        let value = value.trim();
+        let (date, time) = match value.split_once(' ') {
+            Some((date, time)) => (date, Some(time.trim())),
+            None => (value, None),
+        };
+
+        let date = match date.split('/').collect::<Vec<&str>>().as_slice() {
+            [day, month, year] => Self::ymd(
+                number(value, year, 4)?,
+                number(value, month, 2)?,
+                number(value, day, 2)?,
+            )?,
+            _ => return Err(invalid(value, "expected dd/mm/yyyy")),
+        };
+
+        if let Some(time) = time {
+            check_time(value, time)?;
+        }
+
+        Ok(date)
+    }
+
+    /// Parse a date according to its `xsi:type`: W3CDTF dates must follow the W3CDTF profile, untyped dates
+    /// may also use the `dd/mm/yyyy` form.
+    pub fn parse_typed(value: &str, r#type: Option<&str>) -> Result<Self, Error> {
+        match r#type {
+            Some(W3CDTF) => Self::parse_w3cdtf(value),
+            _ => value.parse(),
+        }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> Option<u8> {
+        self.month
+    }
+
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    pub fn precision(&self) -> DatePrecision {
+        match (self.month, self.day) {
+            (Some(_), Some(_)) => DatePrecision::Day,
+            (Some(_), None) => DatePrecision::Month,
+            _ => DatePrecision::Year,
+        }
+    }
+}
+
+impl FromStr for TefDate {
+    type Err = Error;
+
+    /// Parse a date in any of the supported forms
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.contains('/') {
+            Self::parse_french(value)
+        } else {
+            Self::parse_w3cdtf(value)
+        }
+    }
+}
+
+/// W3CDTF form of the date, at its precision
+impl Display for TefDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.year)?;
+
+        if let Some(month) = self.month {
+            write!(f, "-{:02}", month)?;
+        }
+        if let Some(day) = self.day {
+            write!(f, "-{:02}", day)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid<V: Into<String>>(value: V, reason: &'static str) -> Error {
+    Error::InvalidDate {
+        value: value.into(),
+        reason,
+    }
+}
+
+/// Number made of exactly `digits` ASCII digits
+fn number<T: FromStr>(value: &str, part: &str, digits: usize) -> Result<T, Error> {
+    if part.len() != digits || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid(value, "unexpected number of digits"));
+    }
+
+    part.parse()
+        .map_err(|_| invalid(value, "number out of range"))
+}
+
+/// `hh:mm`, `hh:mm:ss` or `hh:mm:ss.s`, followed by an optional time zone (`Z`, `+hh:mm` or `-hh:mm`)
+fn check_time(value: &str, time: &str) -> Result<(), Error> {
+    let (time, zone) = match time.find(['Z', '+', '-']) {
+        Some(index) => time.split_at(index),
+        None => (time, ""),
+    };
+
+    let parts: Vec<&str> = time.split(':').collect();
+    let (hour, minute, second) = match parts.as_slice() {
+        [hour, minute] => (hour, minute, None),
+        [hour, minute, second] => (hour, minute, Some(second)),
+        _ => return Err(invalid(value, "expected hh:mm or hh:mm:ss")),
+    };
+
+    let hour: u8 = number(value, hour, 2)?;
+    let minute: u8 = number(value, minute, 2)?;
+    let second: u8 = match second {
+        Some(second) => {
+            let (second, fraction) = second.split_once('.').unwrap_or((second, "0"));
+            if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid(value, "invalid fraction of second"));
+            }
+            number(value, second, 2)?
+        }
+        None => 0,
+    };
+
+    if hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid(value, "time out of range"));
+    }
+
+    match zone {
+        "" | "Z" => Ok(()),
+        zone => {
+            let (hours, minutes) = zone[1..]
+                .split_once(':')
+                .ok_or_else(|| invalid(value, "expected a time zone as +hh:mm"))?;
+            let hours: u8 = number(value, hours, 2)?;
+            let minutes: u8 = number(value, minutes, 2)?;
+
+            if hours > 23 || minutes > 59 {
+                return Err(invalid(value, "time zone out of range"));
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn w3cdtf_precision() {
+        let year = TefDate::parse_w3cdtf("2019").unwrap();
+        let month = TefDate::parse_w3cdtf("2019-12").unwrap();
+        let day = TefDate::parse_w3cdtf("2019-12-01T10:00:00+01:00").unwrap();
+
+        assert_eq!(year.precision(), DatePrecision::Year);
+        assert_eq!(month.precision(), DatePrecision::Month);
+        assert_eq!(day.precision(), DatePrecision::Day);
+        assert_eq!(
+            (day.year(), day.month(), day.day()),
+            (2019, Some(12), Some(1))
+        );
+        assert!(TefDate::parse_w3cdtf("2019-12T10:00:00").is_err());
+        assert!(TefDate::parse_w3cdtf("19-12-01").is_err());
+    }
+
+    #[test]
+    fn french() {
+        assert_eq!(
+            TefDate::parse_french("01/12/2019 10:00:00").unwrap(),
+            TefDate::ymd(2019, 12, 1).unwrap()
+        );
+        assert!(TefDate::parse_french("2019-12-01").is_err());
+        assert!(TefDate::parse_french("01/13/2019").is_err());
+    }
+
+    #[test]
+    fn invalid_days() {
+        assert!(TefDate::parse_w3cdtf("2019-02-29").is_err());
+        assert!(TefDate::parse_french("29/02/2019").is_err());
+        assert!(TefDate::parse_french("29/02/1900").is_err());
+        assert!(TefDate::parse_w3cdtf("2000-02-29").is_ok());
+        assert!(TefDate::parse_w3cdtf("2020-04-31").is_err());
+        assert!(TefDate::parse_w3cdtf("2020-04-00").is_err());
+    }
+
+    #[test]
+    fn ordering() {
+        let dates: Vec<TefDate> = ["2019", "2019-01", "2019-01-01", "2019-02", "2020"]
+            .iter()
+            .map(|date| date.parse().unwrap())
+            .collect();
+
+        assert!(dates.windows(2).all(|dates| dates[0] < dates[1]));
+    }
+}
@@ -1,4 +1,4 @@
-use getset::Getters;
+use getset::{Getters, MutGetters, Setters};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "lax")]
@@ -10,15 +10,17 @@ use lax_derive::lax;
 /// Ensemble des métadonnées relatives au périmètre ou au domaine d'application du contenu de la ressource
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct Coverage(#[serde(rename = "$text")] String);
+pub struct Coverage(#[serde(rename = "$text")] pub String);
 
 /// Identifiant
 /// dc:identifier
 /// <http://purl.org/dc/elements/1.1/>
 /// Identifiant unique
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Identifier {
     #[serde(rename = "@type")]
     r#type: String,
@@ -26,13 +28,24 @@ pub struct Identifier {
     text: String,
 }
 
+impl Identifier {
+    pub fn new<K: Into<String>, T: Into<String>>(r#type: K, text: T) -> Self {
+        Self {
+            r#type: r#type.into(),
+            text: text.into(),
+        }
+    }
+}
+
 /// Langue
 /// dc:language
 /// <http://purl.org/dc/elements/1.1/>
 /// Langue de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Language {
     #[serde(rename = "@type")]
     r#type: String,
@@ -40,13 +53,25 @@ pub struct Language {
     text: String,
 }
 
+impl Language {
+    /// Language code, typed `dcterms:RFC3066`
+    pub fn new<T: Into<String>>(text: T) -> Self {
+        Self {
+            r#type: "dcterms:RFC3066".to_string(),
+            text: text.into(),
+        }
+    }
+}
+
 /// Sujet
 /// dc:subject
 /// <http://purl.org/dc/elements/1.1/>
 /// Sujet, discipline et/ou mots-clés attribués à la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Subject {
     #[serde(rename = "@lang")]
     lang: Option<String>,
@@ -56,13 +81,25 @@ pub struct Subject {
     text: String,
 }
 
+impl Subject {
+    pub fn new<L: Into<String>, T: Into<String>>(lang: L, text: T) -> Self {
+        Self {
+            lang: Some(lang.into()),
+            r#type: None,
+            text: text.into(),
+        }
+    }
+}
+
 /// Titre et sous-titre
 /// dc:title
 /// <http://purl.org/dc/elements/1.1/>
 /// Titre propre de la thèse et son sous-titre
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Title {
     #[serde(rename = "@lang")]
     lang: String,
@@ -72,16 +109,45 @@ pub struct Title {
     text: String,
 }
 
+impl Title {
+    pub fn new<L: Into<String>, T: Into<String>>(lang: L, text: T) -> Self {
+        Self {
+            lang: lang.into(),
+            r#type: None,
+            text: text.into(),
+        }
+    }
+}
+
 /// Type
 /// dc:type
 /// <http://purl.org/dc/elements/1.1/>
 /// Type
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Type {
     #[serde(rename = "@type")]
     r#type: Option<String>,
     #[serde(rename = "$text")]
     text: String,
 }
+
+impl Type {
+    pub fn new<T: Into<String>>(text: T) -> Self {
+        Self {
+            r#type: None,
+            text: text.into(),
+        }
+    }
+
+    /// Type from a vocabulary given by its `xsi:type`, e.g. `dcterms:DCMIType`
+    pub fn typed<K: Into<String>, T: Into<String>>(r#type: K, text: T) -> Self {
+        Self {
+            r#type: Some(r#type.into()),
+            text: text.into(),
+        }
+    }
+}
@@ -1,16 +1,21 @@
-use getset::Getters;
+use getset::{Getters, MutGetters, Setters};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "lax")]
 use lax_derive::lax;
 
+use crate::date::{TefDate, W3CDTF};
+use crate::error::Error;
+
 /// Résumé
 /// dcterms:abstract
 /// <http://purl.org/dc/terms/>
 /// Résumé de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Abstract {
     #[serde(rename = "@lang")]
     lang: String,
@@ -18,13 +23,24 @@ pub struct Abstract {
     text: String,
 }
 
+impl Abstract {
+    pub fn new<L: Into<String>, T: Into<String>>(lang: L, text: T) -> Self {
+        Self {
+            lang: lang.into(),
+            text: text.into(),
+        }
+    }
+}
+
 /// Titre et sous-titres traduits
 /// dcterms:alternative
 /// <http://purl.org/dc/terms/>
 /// Traduction du titre et du sous-titre de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Alternative {
     #[serde(rename = "@lang")]
     lang: String,
@@ -32,13 +48,24 @@ pub struct Alternative {
     text: String,
 }
 
+impl Alternative {
+    pub fn new<L: Into<String>, T: Into<String>>(lang: L, text: T) -> Self {
+        Self {
+            lang: lang.into(),
+            text: text.into(),
+        }
+    }
+}
+
 /// Date de soutenance
 /// dcterms:dateAccepted
 /// <http://purl.org/dc/terms/>
 /// Date de soutenance de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct DateAccepted {
     #[serde(rename = "@type")]
     r#type: String,
@@ -46,21 +73,38 @@ pub struct DateAccepted {
     text: String,
 }
 
+impl DateAccepted {
+    /// Date typed `dcterms:W3CDTF`
+    pub fn new(date: TefDate) -> Self {
+        Self {
+            r#type: W3CDTF.to_string(),
+            text: date.to_string(),
+        }
+    }
+
+    /// Date, read according to its `xsi:type`
+    pub fn date(&self) -> Result<TefDate, Error> {
+        TefDate::parse_typed(&self.text, Some(&self.r#type))
+    }
+}
+
 /// Taille de fichier
 /// dcterms:extent
 /// <http://purl.org/dc/terms/>
 /// Taille de fichier informatique en octets
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct Extent(#[serde(rename = "$text")] String);
+pub struct Extent(#[serde(rename = "$text")] pub String);
 
 /// Date de publication
 /// dcterms:issued
 /// <http://purl.org/dc/terms/>
 /// Date de publication d'une édition de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Issued {
     #[serde(rename = "@type")]
     r#type: String,
@@ -68,13 +112,22 @@ pub struct Issued {
     text: String,
 }
 
+impl Issued {
+    /// Date, read according to its `xsi:type`
+    pub fn date(&self) -> Result<TefDate, Error> {
+        TefDate::parse_typed(&self.text, Some(&self.r#type))
+    }
+}
+
 /// Type de fichier
 /// dcterms:medium
 /// <http://purl.org/dc/terms/>
 /// Type de fichier informatique
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Medium {
     #[serde(rename = "@type")]
     r#type: String,
@@ -87,8 +140,10 @@ pub struct Medium {
 /// <http://purl.org/dc/terms/>
 /// Renvoie à une autre ressource que la ressource décrite remplace ou à laquelle elle succède
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Replaces {
     #[serde(rename = "@type")]
     r#type: String,
@@ -101,8 +156,10 @@ pub struct Replaces {
 /// <http://purl.org/dc/terms/>
 /// Couverture spatiale du sujet traité dans la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Spatial {
     #[serde(rename = "@type")]
     r#type: Option<String>,
@@ -117,8 +174,10 @@ pub struct Spatial {
 /// <http://purl.org/dc/terms/>
 /// Table des matières de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct TableOfContents {
     #[serde(rename = "@lang")]
     lang: String,
@@ -131,8 +190,10 @@ pub struct TableOfContents {
 /// <http://purl.org/dc/terms/>
 /// Couverture temporelle du sujet traité dans la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Temporal {
     #[serde(rename = "@type")]
     r#type: Option<String>,
@@ -0,0 +1,547 @@
+use std::cell::RefCell;
+
+use getset::Getters;
+use quick_xml::events::Event;
+use quick_xml::{DeError, Reader};
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Location};
+use crate::raw::{self, Others};
+
+mod track;
+
+use track::{State, Tracked};
+
+/// How to handle mandatory elements missing from a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    /// Fail on the first missing mandatory element
+    #[cfg_attr(not(feature = "lax"), default)]
+    Strict,
+    /// Fill missing mandatory elements with their default value and report them
+    #[cfg_attr(feature = "lax", default)]
+    Lenient,
+}
+
+/// Options of the TEF parser
+///
+/// The default mode is strict, or lenient when the `lax` feature is enabled. Both modes are available either way,
+/// the feature also gives a default value to the missing mandatory fields when the types are deserialized with
+/// serde directly, without it they are `missing field` errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    pub mode: ParseMode,
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        Self {
+            mode: ParseMode::Strict,
+        }
+    }
+
+    pub fn lenient() -> Self {
+        Self {
+            mode: ParseMode::Lenient,
+        }
+    }
+}
+
+/// Diagnostics of a parsed document
+///
+/// Defaulted fields are only filled in lenient mode, the path of a missing attribute ends with its name, e.g.
+/// `.../thesisRecord/title/@lang`. A missing repeated element is an empty list, it isn't reported as defaulted. Ignored elements are elements the crate doesn't model, they are reported in
+/// both modes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct ParseReport {
+    defaulted: Vec<Location>,
+    ignored: Vec<Location>,
+}
+
+impl ParseReport {
+    /// Whether the document was read without defaulting or ignoring anything
+    pub fn is_clean(&self) -> bool {
+        self.defaulted.is_empty() && self.ignored.is_empty()
+    }
+
+    /// Names of the ignored elements, without duplicates
+    pub fn ignored_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .ignored
+            .iter()
+            .filter_map(|location| location.path().rsplit('/').next())
+            .map(|name| name.split('[').next().unwrap_or(name))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+/// Step of the path of a deserialized value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Segment {
+    /// Struct field or map key
+    Map(String),
+    /// Position in a sequence
+    Seq(usize),
+    /// Enum variant
+    Enum(String),
+    Unknown,
+}
+
+impl From<&serde_path_to_error::Segment> for Segment {
+    fn from(segment: &serde_path_to_error::Segment) -> Self {
+        match segment {
+            serde_path_to_error::Segment::Map { key } => Segment::Map(key.clone()),
+            serde_path_to_error::Segment::Seq { index } => Segment::Seq(*index),
+            serde_path_to_error::Segment::Enum { variant } => Segment::Enum(variant.clone()),
+            _ => Segment::Unknown,
+        }
+    }
+}
+
+thread_local! {
+    /// Mandatory fields defaulted by the structs being deserialized, `None` outside of the parser
+    static DEFAULTED: RefCell<Option<Vec<&'static str>>> = const { RefCell::new(None) };
+}
+
+/// Default value of a missing mandatory field, used by the `lax` attribute of the `lax` feature.
+///
+/// The field is recorded so the parser still rejects it in strict mode, lenient mode fills the missing fields
+/// itself. Outside of the parser, e.g. when a type is deserialized directly with serde, the default is silently
+/// used.
+#[cfg(feature = "lax")]
+pub(crate) fn defaulted<T: Default>(field: &'static str) -> T {
+    DEFAULTED.with(|defaulted| {
+        if let Some(defaulted) = defaulted.borrow_mut().as_mut() {
+            defaulted.push(field);
+        }
+    });
+
+    T::default()
+}
+
+fn defaulted_len() -> usize {
+    DEFAULTED.with(|defaulted| defaulted.borrow().as_ref().map(Vec::len).unwrap_or(0))
+}
+
+fn take_defaulted(mark: usize) -> Vec<&'static str> {
+    DEFAULTED.with(|defaulted| match defaulted.borrow_mut().as_mut() {
+        Some(defaulted) if defaulted.len() > mark => defaulted.drain(mark..).collect(),
+        _ => vec![],
+    })
+}
+
+/// Record the defaulted fields while alive
+struct Recording(Option<Vec<&'static str>>);
+
+impl Recording {
+    fn start() -> Self {
+        Recording(DEFAULTED.with(|defaulted| defaulted.replace(Some(vec![]))))
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        DEFAULTED.with(|defaulted| *defaulted.borrow_mut() = self.0.take());
+    }
+}
+
+/// Deserialize a document, locating the faulty element on failure.
+///
+/// The defaulted fields and ignored elements are reported along with the value.
+pub(crate) fn from_str<T: DeserializeOwned + Others>(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<(T, ParseReport), Error> {
+    let _recording = Recording::start();
+    let state = State::new(options.mode);
+    let mut de = quick_xml::de::Deserializer::from_str(input);
+
+    match T::deserialize(Tracked::new(&mut de, &state)) {
+        Ok(value) => finish(input, value, state),
+        Err(_) => locate::<T>(input, options),
+    }
+}
+
+/// Run the deserialization again while tracking the path of the deserialized fields.
+fn locate<T: DeserializeOwned + Others>(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<(T, ParseReport), Error> {
+    let state = State::new(options.mode);
+    let mut de = quick_xml::de::Deserializer::from_str(input);
+
+    match serde_path_to_error::deserialize(Tracked::new(&mut de, &state)) {
+        Ok(value) => finish(input, value, state),
+        Err(err) => {
+            let path: Vec<Segment> = err.path().iter().map(Segment::from).collect();
+
+            Err(match err.into_inner() {
+                DeError::InvalidXml(source) => Error::Syntax {
+                    source,
+                    location: syntax_location(input),
+                },
+                // a missing mandatory element or attribute is located like the lenient defaults
+                DeError::Custom(message) if missing_field(&message).is_some() => {
+                    let mut path = path;
+                    path.extend(missing_field(&message).map(|field| Segment::Map(field.into())));
+
+                    let location = defaulted_locations(&Tree::parse(input), vec![path]).remove(0);
+                    Error::missing(location.path().clone())
+                }
+                source => Error::Deserialize {
+                    source,
+                    location: element_location(&Tree::parse(input), &path),
+                },
+            })
+        }
+    }
+}
+
+/// Name of the field of a serde `missing field` error
+fn missing_field(message: &str) -> Option<&str> {
+    message.strip_prefix("missing field `")?.strip_suffix('`')
+}
+
+/// Read the content of the unknown elements and report the defaulted fields and ignored elements.
+fn finish<T: Others>(input: &str, mut value: T, state: State) -> Result<(T, ParseReport), Error> {
+    let (defaulted, ignored, others) = state.into_parts();
+
+    if defaulted.is_empty() && ignored.is_empty() && others.is_empty() {
+        return Ok((value, ParseReport::default()));
+    }
+
+    let tree = Tree::parse(input);
+
+    if !others.is_empty() {
+        let mut nodes: Vec<usize> = others
+            .iter()
+            .map(|path| element_node(&tree, path))
+            .collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+
+        let mut captured = raw::capture(input, &nodes)?.into_iter();
+
+        // lenient defaults are unnamed, only the elements read from the document are filled
+        for other in value.others_mut() {
+            if other.name().is_empty() {
+                continue;
+            }
+
+            if let Some(raw) = captured.find(|raw| raw.local_name() == other.local_name()) {
+                *other = raw;
+            }
+        }
+    }
+
+    let report = ParseReport {
+        defaulted: defaulted_locations(&tree, defaulted),
+        ignored: ignored
+            .iter()
+            .map(|path| element_location(&tree, path))
+            .collect(),
+    };
+
+    Ok((value, report))
+}
+
+/// Location of the defaulted fields, attributes and texts are named after the element holding them.
+fn defaulted_locations(tree: &Tree, defaulted: Vec<Vec<Segment>>) -> Vec<Location> {
+    defaulted
+        .into_iter()
+        .map(|mut path| {
+            let field = match path.pop() {
+                Some(Segment::Map(field)) => field,
+                _ => String::new(),
+            };
+            let parent = element_location(tree, &path);
+
+            let path = match field.as_str() {
+                "$text" | "$value" | "" => parent.path().clone(),
+                field => format!("{}/{}", parent.path(), field),
+            };
+
+            Location::new(path, *parent.offset())
+        })
+        .collect()
+}
+
+/// Element of the document, as read by [`Tree::parse`]
+struct Node {
+    name: String,
+    offset: usize,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+/// Minimal view of the elements of a document
+struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    /// Read the elements of the document, up to the first syntax error.
+    fn parse(input: &str) -> Self {
+        let mut reader = Reader::from_str(input);
+        let mut nodes: Vec<Node> = vec![];
+        let mut current: Option<usize> = None;
+
+        loop {
+            let offset = reader.buffer_position();
+
+            let (name, empty) = match reader.read_event() {
+                Ok(Event::Start(e)) => (e.local_name().as_ref().to_vec(), false),
+                Ok(Event::Empty(e)) => (e.local_name().as_ref().to_vec(), true),
+                Ok(Event::End(_)) => {
+                    current = current.and_then(|index| nodes[index].parent);
+                    continue;
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                Ok(_) => continue,
+            };
+
+            let index = nodes.len();
+            nodes.push(Node {
+                name: String::from_utf8_lossy(&name).into_owned(),
+                offset,
+                parent: current,
+                children: vec![],
+            });
+
+            if let Some(parent) = current {
+                nodes[parent].children.push(index);
+            }
+
+            if !empty {
+                current = Some(index);
+            }
+        }
+
+        Self { nodes }
+    }
+
+    fn children_named<'a>(
+        &'a self,
+        node: usize,
+        name: &'a str,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.nodes[node]
+            .children
+            .iter()
+            .copied()
+            .filter(move |child| self.nodes[*child].name == name)
+    }
+
+    /// Path of a node, the position among siblings of the same name is added when there is more than one.
+    fn path(&self, node: usize) -> String {
+        let mut parts = vec![];
+        let mut current = Some(node);
+
+        while let Some(node) = current {
+            let name = &self.nodes[node].name;
+
+            match self.nodes[node].parent {
+                Some(parent) => {
+                    let siblings: Vec<usize> = self.children_named(parent, name).collect();
+
+                    if siblings.len() > 1 {
+                        let position = siblings.iter().position(|n| *n == node).unwrap_or(0);
+                        parts.push(format!("{}[{}]", name, position + 1));
+                    } else {
+                        parts.push(name.clone());
+                    }
+                }
+                None => parts.push(name.clone()),
+            }
+
+            current = self.nodes[node].parent;
+        }
+
+        parts.reverse();
+        parts.join("/")
+    }
+}
+
+/// Follow the path of the deserialized fields in the document.
+///
+/// Fields are named after the elements they are read from, except `$value` fields which take every child element.
+fn element_location(tree: &Tree, path: &[Segment]) -> Location {
+    if tree.nodes.is_empty() {
+        return Location::default();
+    }
+
+    let node = element_node(tree, path);
+
+    Location::new(tree.path(node), Some(tree.nodes[node].offset))
+}
+
+/// Index of the element at the end of the path, see [`element_location`]
+fn element_node(tree: &Tree, path: &[Segment]) -> usize {
+    let mut node = 0;
+    let mut segments = path.iter().peekable();
+
+    while let Some(segment) = segments.next() {
+        let key = match segment {
+            Segment::Map(key) => key,
+            Segment::Enum(_) => continue,
+            _ => break,
+        };
+
+        // attributes and text belong to the current element
+        if key.starts_with('@') || key == "$text" {
+            break;
+        }
+
+        let index = match segments.peek() {
+            Some(Segment::Seq(index)) => {
+                let index = *index;
+                segments.next();
+                index
+            }
+            _ => 0,
+        };
+
+        let child = if key == "$value" {
+            tree.nodes[node].children.get(index).copied()
+        } else {
+            tree.children_named(node, key).nth(index)
+        };
+
+        match child {
+            Some(child) => node = child,
+            None => break,
+        }
+    }
+
+    node
+}
+
+/// Locate a syntax error by reading the document up to it.
+fn syntax_location(input: &str) -> Location {
+    let mut reader = Reader::from_str(input);
+    let mut stack: Vec<String> = vec![];
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                stack.push(String::from_utf8_lossy(e.local_name().as_ref()).into_owned())
+            }
+            Ok(Event::End(_)) => {
+                stack.pop();
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+
+    Location::new(stack.join("/"), Some(reader.buffer_position()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{these_with, THESE};
+    use crate::parse_tef_with;
+
+    fn strict_error(input: &str) -> String {
+        match parse_tef_with(input, &ParseOptions::strict()) {
+            Err(Error::MissingElement { path }) => path,
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    fn without_title() -> String {
+        let start = THESE.find("<dc:title").unwrap();
+        let end = THESE.find("<dcterms:alternative").unwrap();
+        format!("{}{}", &THESE[..start], &THESE[end..])
+    }
+
+    #[test]
+    fn missing_element() {
+        let input = without_title();
+
+        assert_eq!(
+            strict_error(&input),
+            "mets/dmdSec[1]/mdWrap/xmlData/thesisRecord/title"
+        );
+        assert_eq!(
+            strict_error(&these_with(&[(
+                r#"<dc:title xml:lang="fr">"#,
+                "<dc:title>"
+            )])),
+            "mets/dmdSec[1]/mdWrap/xmlData/thesisRecord/title/@lang"
+        );
+    }
+
+    #[test]
+    fn lenient_without_lax_defaults() {
+        let input = without_title();
+
+        // the types only default their fields with the `lax` feature
+        #[cfg(not(feature = "lax"))]
+        assert!(quick_xml::de::from_str::<crate::Mets>(&input).is_err());
+
+        let (mets, report) = parse_tef_with(&input, &ParseOptions::lenient()).unwrap();
+        let defaulted: Vec<&str> = report
+            .defaulted()
+            .iter()
+            .map(|location| location.path().as_str())
+            .collect();
+
+        assert_eq!(mets.thesis_record().unwrap().title().text(), "");
+        assert_eq!(
+            defaulted[0],
+            "mets/dmdSec[1]/mdWrap/xmlData/thesisRecord/title"
+        );
+    }
+
+    #[test]
+    fn report() {
+        let input = without_title()
+            .replace(r#"<dcterms:abstract xml:lang="en">"#, "<dcterms:abstract>")
+            .replace(
+                "<dc:type>",
+                "<tef:inconnu>a</tef:inconnu><tef:inconnu>b</tef:inconnu><dc:type>",
+            );
+        let (_, report) = parse_tef_with(&input, &ParseOptions::lenient()).unwrap();
+        let paths = |locations: &[Location]| -> Vec<String> {
+            locations
+                .iter()
+                .map(|location| location.path().clone())
+                .filter(|path| !path.ends_with("/vie"))
+                .collect()
+        };
+        let record = "mets/dmdSec[1]/mdWrap/xmlData/thesisRecord";
+
+        assert!(!report.is_clean());
+        assert_eq!(
+            paths(report.defaulted()),
+            [
+                format!("{record}/abstract[2]/@lang"),
+                format!("{record}/title")
+            ]
+        );
+        assert_eq!(
+            paths(report.ignored()),
+            [
+                format!("{record}/inconnu[1]"),
+                format!("{record}/inconnu[2]")
+            ]
+        );
+        assert_eq!(report.ignored_names(), ["inconnu"]);
+        assert!(report
+            .defaulted()
+            .iter()
+            .chain(report.ignored())
+            .all(|location| location.offset().is_some()));
+
+        let (_, report) = parse_tef_with(THESE, &ParseOptions::lenient()).unwrap();
+        assert!(paths(report.defaulted()).is_empty());
+        assert!(report.ignored().is_empty());
+    }
+}
@@ -0,0 +1,950 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, IgnoredAny, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+
+use super::{defaulted_len, take_defaulted, ParseMode, Segment};
+use crate::raw::RAW_ELEMENT;
+
+pub(crate) type Parts = (Vec<Vec<Segment>>, Vec<Vec<Segment>>, Vec<Vec<Segment>>);
+
+/// Path of the value being deserialized, fields defaulted and elements ignored so far
+pub(crate) struct State {
+    mode: ParseMode,
+    path: RefCell<Vec<Segment>>,
+    defaulted: RefCell<Vec<Vec<Segment>>>,
+    ignored: RefCell<Vec<Vec<Segment>>>,
+    others: RefCell<Vec<Vec<Segment>>>,
+}
+
+impl State {
+    pub(crate) fn new(mode: ParseMode) -> Self {
+        Self {
+            mode,
+            path: RefCell::new(vec![]),
+            defaulted: RefCell::new(vec![]),
+            ignored: RefCell::new(vec![]),
+            others: RefCell::new(vec![]),
+        }
+    }
+
+    /// Path of each defaulted field, the last segment being the name of the field, path of each ignored
+    /// element and path of each element kept in an `Other` variant
+    pub(crate) fn into_parts(self) -> Parts {
+        (
+            self.defaulted.into_inner(),
+            self.ignored.into_inner(),
+            self.others.into_inner(),
+        )
+    }
+
+    /// Record the value being deserialized as defaulted
+    fn report_default(&self) {
+        let path = self.path.borrow().clone();
+        self.defaulted.borrow_mut().push(path);
+    }
+
+    /// Record the element being deserialized as read afterwards from the document
+    fn other(&self) {
+        let path = self.path.borrow().clone();
+        self.others.borrow_mut().push(path);
+    }
+
+    /// Local name of the element being deserialized, from the field holding it
+    fn element_name(&self) -> String {
+        self.path
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|segment| match segment {
+                Segment::Map(key) => Some(key.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn ignore(&self, segments: &[Segment]) {
+        let mut path = self.path.borrow().clone();
+        path.extend_from_slice(segments);
+        self.ignored.borrow_mut().push(path);
+    }
+
+    /// Whether the value being deserialized is an attribute or a text
+    fn in_attribute(&self) -> bool {
+        matches!(self.path.borrow().last(), Some(Segment::Map(key)) if key.starts_with('@') || key == "$text")
+    }
+
+    fn with_segment<T, F: FnOnce() -> T>(&self, segment: Segment, f: F) -> T {
+        self.path.borrow_mut().push(segment);
+        let result = f();
+        self.path.borrow_mut().pop();
+        result
+    }
+
+    /// Reject the defaulted fields of a struct in strict mode, record them in lenient mode.
+    fn check<E: de::Error>(&self, fields: Vec<&'static str>) -> Result<(), E> {
+        match self.mode {
+            ParseMode::Strict => match fields.first() {
+                Some(field) => Err(E::missing_field(field)),
+                None => Ok(()),
+            },
+            ParseMode::Lenient => {
+                let path = self.path.borrow();
+
+                self.defaulted
+                    .borrow_mut()
+                    .extend(fields.into_iter().map(|field| {
+                        let mut path = path.clone();
+                        path.push(Segment::Map(field.to_string()));
+                        path
+                    }));
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Forward the `deserialize_*` methods, wrapping the visitor
+macro_rules! forward_deserializer {
+    () => {
+        forward_deserializer!(@methods
+            deserialize_any(),
+            deserialize_bool(),
+            deserialize_i8(),
+            deserialize_i16(),
+            deserialize_i32(),
+            deserialize_i64(),
+            deserialize_i128(),
+            deserialize_u8(),
+            deserialize_u16(),
+            deserialize_u32(),
+            deserialize_u64(),
+            deserialize_u128(),
+            deserialize_f32(),
+            deserialize_f64(),
+            deserialize_char(),
+            deserialize_str(),
+            deserialize_string(),
+            deserialize_bytes(),
+            deserialize_byte_buf(),
+            deserialize_option(),
+            deserialize_unit(),
+            deserialize_unit_struct(name: &'static str),
+            deserialize_newtype_struct(name: &'static str),
+            deserialize_seq(),
+            deserialize_tuple(len: usize),
+            deserialize_tuple_struct(name: &'static str, len: usize),
+            deserialize_map(),
+            deserialize_identifier(),
+            deserialize_ignored_any(),
+        );
+
+        fn is_human_readable(&self) -> bool {
+            self.de.is_human_readable()
+        }
+    };
+    (@methods $($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+                self.de.$method($($arg,)* TrackedVisitor::new(visitor, self.state, &[]))
+            }
+        )*
+    };
+}
+
+/// Forward the `visit_*` methods that don't give access to nested values
+macro_rules! forward_visitor {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+/// Deserializer keeping track of the path of the value being deserialized
+pub(crate) struct Tracked<'s, D> {
+    de: D,
+    state: &'s State,
+}
+
+impl<'s, D> Tracked<'s, D> {
+    pub(crate) fn new(de: D, state: &'s State) -> Self {
+        Self { de, state }
+    }
+}
+
+impl<'de, 's, D: Deserializer<'de>> Deserializer<'de> for Tracked<'s, D> {
+    type Error = D::Error;
+
+    forward_deserializer!();
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // the content of a raw element is read afterwards from the document
+        if name == RAW_ELEMENT {
+            self.de.deserialize_ignored_any(IgnoredAny)?;
+            self.state.other();
+            return visitor.visit_string(self.state.element_name());
+        }
+
+        self.de.deserialize_struct(
+            name,
+            fields,
+            TrackedVisitor::new(visitor, self.state, fields),
+        )
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.de.deserialize_enum(
+            name,
+            variants,
+            TrackedVisitor::new(visitor, self.state, variants),
+        )
+    }
+}
+
+/// Visitor wrapping the accessors it is given
+///
+/// `known` holds the fields of the struct or the variants of the enum being deserialized, any other name is an
+/// ignored element.
+struct TrackedVisitor<'s, V> {
+    visitor: V,
+    state: &'s State,
+    known: &'static [&'static str],
+}
+
+impl<'s, V> TrackedVisitor<'s, V> {
+    fn new(visitor: V, state: &'s State, known: &'static [&'static str]) -> Self {
+        Self {
+            visitor,
+            state,
+            known,
+        }
+    }
+}
+
+impl<'de, 's, V: Visitor<'de>> Visitor<'de> for TrackedVisitor<'s, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(f)
+    }
+
+    forward_visitor!(
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    );
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.visitor.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+        self.visitor.visit_some(Tracked::new(de, self.state))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+        self.visitor
+            .visit_newtype_struct(Tracked::new(de, self.state))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_seq(TrackedSeq {
+            seq,
+            state: self.state,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        // the fields defaulted by a struct are known once all of its fields have been read
+        let mark = defaulted_len();
+        let result = self.visitor.visit_map(TrackedMap {
+            map,
+            state: self.state,
+            known: self.known,
+            key: None,
+            ignored: HashMap::new(),
+            seen: vec![],
+            missing: None,
+        });
+        let fields = take_defaulted(mark);
+
+        let value = result?;
+        self.state.check(fields)?;
+        Ok(value)
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_enum(TrackedEnum {
+            data,
+            state: self.state,
+            known: self.known,
+        })
+    }
+}
+
+struct TrackedSeed<'s, S> {
+    seed: S,
+    state: &'s State,
+}
+
+impl<'de, 's, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TrackedSeed<'s, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+        self.seed.deserialize(Tracked::new(de, self.state))
+    }
+}
+
+struct TrackedSeq<'s, A> {
+    seq: A,
+    state: &'s State,
+    index: usize,
+}
+
+impl<'de, 's, A: SeqAccess<'de>> SeqAccess<'de> for TrackedSeq<'s, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let index = self.index;
+        self.index += 1;
+
+        let state = self.state;
+        let seq = &mut self.seq;
+
+        state.with_segment(Segment::Seq(index), || {
+            seq.next_element_seed(TrackedSeed { seed, state })
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.seq.size_hint()
+    }
+}
+
+struct TrackedMap<'s, A> {
+    map: A,
+    state: &'s State,
+    known: &'static [&'static str],
+    key: Option<String>,
+    /// Number of ignored elements of each name
+    ignored: HashMap<String, usize>,
+    /// Keys read from the document
+    seen: Vec<String>,
+    /// Position in `known` of the next absent field, once the keys of the document are read
+    missing: Option<usize>,
+}
+
+impl<'s, A> TrackedMap<'s, A> {
+    /// Next field of the struct absent from the document
+    fn next_missing(&mut self) -> Option<&'static str> {
+        let index = self.missing.get_or_insert(0);
+        let field = self.known[*index..]
+            .iter()
+            .find(|field| !self.seen.iter().any(|seen| seen == *field))?;
+        *index = self.known.iter().position(|known| known == field)? + 1;
+
+        Some(field)
+    }
+}
+
+impl<'de, 's, A: MapAccess<'de>> MapAccess<'de> for TrackedMap<'s, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let mut seed = Some(seed);
+
+        if self.missing.is_none() {
+            let key = self.map.next_key_seed(CaptureKey {
+                seed: OnceSeed(&mut seed),
+                key: &mut self.key,
+            })?;
+
+            if key.is_some() {
+                self.seen.extend(self.key.clone());
+                return Ok(key);
+            }
+        }
+
+        // in lenient mode, the absent fields of a struct are given an empty value once the document is read
+        let seed = match seed {
+            Some(seed) if self.state.mode == ParseMode::Lenient && !self.known.is_empty() => seed,
+            _ => return Ok(None),
+        };
+
+        match self.next_missing() {
+            Some(field) => {
+                self.key = Some(field.to_string());
+                seed.deserialize(field.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let key = self.key.take().unwrap_or_default();
+        let state = self.state;
+
+        if self.missing.is_some() {
+            return state.with_segment(Segment::Map(key), || {
+                seed.deserialize(Tracked::new(Missing::new(Some(state)), state))
+            });
+        }
+
+        // attributes and texts aren't elements, maps other than structs have no known keys
+        if !self.known.is_empty()
+            && !key.starts_with('@')
+            && key != "$text"
+            && !self.known.contains(&key.as_str())
+        {
+            let index = self.ignored.entry(key.clone()).or_default();
+            state.ignore(&[Segment::Map(key.clone()), Segment::Seq(*index)]);
+            *index += 1;
+        }
+
+        let map = &mut self.map;
+
+        state.with_segment(Segment::Map(key), || {
+            map.next_value_seed(TrackedSeed { seed, state })
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+struct TrackedEnum<'s, A> {
+    data: A,
+    state: &'s State,
+    known: &'static [&'static str],
+}
+
+impl<'de, 's, A: EnumAccess<'de>> EnumAccess<'de> for TrackedEnum<'s, A> {
+    type Error = A::Error;
+    type Variant = TrackedVariant<'s, A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let mut name = None;
+        let (value, variant) = self.data.variant_seed(CaptureKey {
+            seed,
+            key: &mut name,
+        })?;
+        let name = name.unwrap_or_default();
+
+        // unknown elements fall in an `Other` variant, unknown attribute values aren't elements
+        if !self.known.contains(&name.as_str()) && !self.state.in_attribute() {
+            self.state.ignore(&[]);
+            self.state.other();
+        }
+
+        Ok((
+            value,
+            TrackedVariant {
+                variant,
+                state: self.state,
+                name,
+            },
+        ))
+    }
+}
+
+struct TrackedVariant<'s, A> {
+    variant: A,
+    state: &'s State,
+    name: String,
+}
+
+impl<'de, 's, A: VariantAccess<'de>> VariantAccess<'de> for TrackedVariant<'s, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.variant.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        let state = self.state;
+        let variant = self.variant;
+
+        state.with_segment(Segment::Enum(self.name), || {
+            variant.newtype_variant_seed(TrackedSeed { seed, state })
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let state = self.state;
+        let variant = self.variant;
+
+        state.with_segment(Segment::Enum(self.name), || {
+            variant.tuple_variant(len, TrackedVisitor::new(visitor, state, &[]))
+        })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let state = self.state;
+        let variant = self.variant;
+
+        state.with_segment(Segment::Enum(self.name), || {
+            variant.struct_variant(fields, TrackedVisitor::new(visitor, state, fields))
+        })
+    }
+}
+
+/// Seed left in place when the map has no more keys
+struct OnceSeed<'a, S>(&'a mut Option<S>);
+
+impl<'de, 'a, S: DeserializeSeed<'de>> DeserializeSeed<'de> for OnceSeed<'a, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+        match self.0.take() {
+            Some(seed) => seed.deserialize(de),
+            None => Err(de::Error::custom("map key already deserialized")),
+        }
+    }
+}
+
+/// Seed saving the name of the map key or enum variant it deserializes
+struct CaptureKey<'k, S> {
+    seed: S,
+    key: &'k mut Option<String>,
+}
+
+impl<'de, 'k, S: DeserializeSeed<'de>> DeserializeSeed<'de> for CaptureKey<'k, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, de: D) -> Result<Self::Value, D::Error> {
+        self.seed
+            .deserialize(CaptureDeserializer { de, key: self.key })
+    }
+}
+
+struct CaptureDeserializer<'k, D> {
+    de: D,
+    key: &'k mut Option<String>,
+}
+
+impl<'de, 'k, D: Deserializer<'de>> Deserializer<'de> for CaptureDeserializer<'k, D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.de.deserialize_any(CaptureVisitor {
+            visitor,
+            key: self.key,
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.de.deserialize_identifier(CaptureVisitor {
+            visitor,
+            key: self.key,
+        })
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.de.deserialize_str(CaptureVisitor {
+            visitor,
+            key: self.key,
+        })
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.de.deserialize_string(CaptureVisitor {
+            visitor,
+            key: self.key,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.de.is_human_readable()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf option unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct enum ignored_any
+    }
+}
+
+struct CaptureVisitor<'k, V> {
+    visitor: V,
+    key: &'k mut Option<String>,
+}
+
+impl<'de, 'k, V: Visitor<'de>> Visitor<'de> for CaptureVisitor<'k, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(f)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        *self.key = Some(v.to_string());
+        self.visitor.visit_str(v)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        *self.key = Some(v.to_string());
+        self.visitor.visit_borrowed_str(v)
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        *self.key = Some(v.clone());
+        self.visitor.visit_string(v)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        *self.key = Some(String::from_utf8_lossy(v).into_owned());
+        self.visitor.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        *self.key = Some(String::from_utf8_lossy(v).into_owned());
+        self.visitor.visit_borrowed_bytes(v)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        self.visitor.visit_u64(v)
+    }
+}
+
+/// Deserializer of a field absent from the document
+///
+/// Optional fields are `None`, mandatory ones get an empty value: empty text, first variant of an enum, and a
+/// struct whose own fields are all absent. Only the field itself is reported as defaulted, not its content.
+/// Lists are left empty without being reported, zero occurrences of a repeated element are checked by the
+/// validation.
+struct Missing<'s, E> {
+    state: Option<&'s State>,
+    error: PhantomData<E>,
+}
+
+impl<'s, E> Missing<'s, E> {
+    fn new(state: Option<&'s State>) -> Self {
+        Self {
+            state,
+            error: PhantomData,
+        }
+    }
+
+    fn report(&self) {
+        if let Some(state) = self.state {
+            state.report_default();
+        }
+    }
+}
+
+impl<'de, 's, E: de::Error> Deserializer<'de> for Missing<'s, E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_f64(0.0)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_str("")
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_bytes(&[])
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_none()
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_newtype_struct(Missing::new(None))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_seq(MissingSeq::<E>::new(0))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_seq(MissingSeq::<E>::new(len))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_map(MissingMap::<E>::new(&[]))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_map(MissingMap::<E>::new(fields))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        self.report();
+        visitor.visit_enum(MissingEnum::<E> {
+            variant: variants.first().copied().unwrap_or_default(),
+            error: PhantomData,
+        })
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char string identifier
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        self.deserialize_tuple(len, visitor)
+    }
+}
+
+/// Elements of an absent list or tuple
+struct MissingSeq<E> {
+    len: usize,
+    error: PhantomData<E>,
+}
+
+impl<E> MissingSeq<E> {
+    fn new(len: usize) -> Self {
+        Self {
+            len,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> SeqAccess<'de> for MissingSeq<E> {
+    type Error = E;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, E> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+
+        self.len -= 1;
+        seed.deserialize(Missing::new(None)).map(Some)
+    }
+}
+
+/// Fields of an absent struct, all of them absent too
+struct MissingMap<E> {
+    fields: std::slice::Iter<'static, &'static str>,
+    error: PhantomData<E>,
+}
+
+impl<E> MissingMap<E> {
+    fn new(fields: &'static [&'static str]) -> Self {
+        Self {
+            fields: fields.iter(),
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> MapAccess<'de> for MissingMap<E> {
+    type Error = E;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, E> {
+        match self.fields.next() {
+            Some(field) => seed.deserialize(field.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, E> {
+        seed.deserialize(Missing::new(None))
+    }
+}
+
+/// First variant of an absent enum
+struct MissingEnum<E> {
+    variant: &'static str,
+    error: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> EnumAccess<'de> for MissingEnum<E> {
+    type Error = E;
+    type Variant = Missing<'static, E>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), E> {
+        let value = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((value, Missing::new(None)))
+    }
+}
+
+impl<'de, 's, E: de::Error> VariantAccess<'de> for Missing<'s, E> {
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, E> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_seq(MissingSeq::<E>::new(len))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        visitor.visit_map(MissingMap::<E>::new(fields))
+    }
+}
@@ -0,0 +1,832 @@
+use std::fmt::Display;
+
+use getset::Getters;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::mets::*;
+use crate::mets_rights::*;
+use crate::tef::*;
+
+#[cfg(feature = "gestion")]
+use crate::gestion::star::{Diffusion, StarGestion};
+
+/// Kind of change between two records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Removed => write!(f, "removed"),
+            ChangeKind::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// Semantic change between two records
+///
+/// `path` names the element, with its key between brackets for repeated elements: the language of an abstract, the
+/// name of a person, the `ID` of a file or of an edition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct Change {
+    kind: ChangeKind,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new: Option<String>,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let old = self.old.as_deref().unwrap_or_default();
+        let new = self.new.as_deref().unwrap_or_default();
+
+        match self.kind {
+            ChangeKind::Added => write!(f, "+ {}: {}", self.path, new),
+            ChangeKind::Removed => write!(f, "- {}: {}", self.path, old),
+            ChangeKind::Modified => write!(f, "~ {}: {} -> {}", self.path, old, new),
+        }
+    }
+}
+
+/// Compare two records element by element: the thesis record and admin data, the persons, organizations, editions,
+/// rights declarations and files. The order of repeated elements is not significant.
+pub fn diff(old: &Mets, new: &Mets) -> Vec<Change> {
+    let (old, new) = (Sections::new(old), Sections::new(new));
+    let mut differ = Differ::default();
+
+    differ.thesis_record(old.record, new.record);
+    differ.thesis_admin(old.admin, new.admin);
+    differ.keyed(
+        "edition",
+        &old.editions.iter().map(edition).collect::<Vec<_>>(),
+        &new.editions.iter().map(edition).collect::<Vec<_>>(),
+    );
+    differ.keyed(
+        "rightsMD",
+        &old.rights.iter().map(rights).collect::<Vec<_>>(),
+        &new.rights.iter().map(rights).collect::<Vec<_>>(),
+    );
+    differ.keyed(
+        "file",
+        &old.files
+            .iter()
+            .map(|file| file_entry(file))
+            .collect::<Vec<_>>(),
+        &new.files
+            .iter()
+            .map(|file| file_entry(file))
+            .collect::<Vec<_>>(),
+    );
+    #[cfg(feature = "gestion")]
+    differ.star_gestion(old.star_gestion, new.star_gestion);
+
+    differ.changes
+}
+
+/// One change per line
+pub fn to_text(changes: &[Change]) -> String {
+    changes
+        .iter()
+        .map(|change| format!("{}\n", change))
+        .collect()
+}
+
+/// JSON array of the changes, with `kind`, `path`, and `old` and `new` when they apply
+pub fn to_json(changes: &[Change]) -> Result<String, Error> {
+    serde_json::to_string_pretty(changes).map_err(|err| Error::Export {
+        format: "JSON",
+        reason: err.to_string(),
+    })
+}
+
+#[cfg(feature = "gestion")]
+type DiffusionField = fn(&Diffusion) -> &String;
+
+/// Sections of a record to compare
+#[derive(Default)]
+struct Sections<'a> {
+    record: Option<&'a ThesisRecord>,
+    admin: Option<&'a ThesisAdmin>,
+    editions: Vec<(&'a str, &'a Edition)>,
+    rights: Vec<(&'a str, &'a RightsDeclarationMD)>,
+    files: Vec<&'a File>,
+    #[cfg(feature = "gestion")]
+    star_gestion: Option<&'a StarGestion>,
+}
+
+impl<'a> Sections<'a> {
+    fn new(mets: &'a Mets) -> Self {
+        let mut sections = Self {
+            record: mets.thesis_record(),
+            admin: mets.thesis_admin(),
+            ..Self::default()
+        };
+
+        for value in mets.values() {
+            match value {
+                MetsValues::DmdSec(dmd_sec) => sections.insert(dmd_sec.id(), dmd_sec.value()),
+                MetsValues::AmdSec(amd_sec) => {
+                    for value in amd_sec.values() {
+                        match value {
+                            AmdSecValues::TechMD(tech_md) => {
+                                sections.insert(tech_md.id(), tech_md.value())
+                            }
+                            AmdSecValues::RightsMD(rights_md) => sections.insert(
+                                rights_md.id().as_deref().unwrap_or_default(),
+                                rights_md.value(),
+                            ),
+                            AmdSecValues::Other(_) => {}
+                        }
+                    }
+                }
+                MetsValues::FileSec(file_sec) => {
+                    for file_grp in file_sec.value() {
+                        sections.insert_files(file_grp);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        sections
+    }
+
+    fn insert(&mut self, id: &'a str, md_wrap: &'a MdWrap) {
+        match md_wrap.value().value() {
+            XmlDataValues::Edition(edition) => self.editions.push((id, edition)),
+            XmlDataValues::RightsDeclarationMD(rights) => self.rights.push((id, rights)),
+            #[cfg(feature = "gestion")]
+            XmlDataValues::StarGestion(star_gestion) => {
+                self.star_gestion.get_or_insert(star_gestion);
+            }
+            _ => {}
+        }
+    }
+
+    fn insert_files(&mut self, file_grp: &'a FileGrp) {
+        for value in file_grp.values() {
+            match value {
+                FileGrpValues::File(file) => self.files.push(file),
+                FileGrpValues::FileGrp(file_grp) => self.insert_files(file_grp),
+            }
+        }
+    }
+}
+
+/// Repeated element: `key` matches the elements of both records, `label` is shown in the path, and `value` is
+/// compared and shown in the change
+#[derive(Debug, Clone)]
+struct Entry {
+    key: String,
+    label: Option<String>,
+    value: String,
+}
+
+impl Entry {
+    /// Element without a key of its own, compared by value
+    fn value<T: Into<String>>(value: T) -> Self {
+        let value = value.into();
+
+        Self {
+            key: value.clone(),
+            label: None,
+            value,
+        }
+    }
+
+    /// Element identified by a key shown in the path
+    fn keyed<K: Into<String>, T: Into<String>>(key: K, value: T) -> Self {
+        let key = key.into();
+
+        Self {
+            label: Some(key.clone()),
+            key,
+            value: value.into(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push<P: Into<String>>(
+        &mut self,
+        kind: ChangeKind,
+        path: P,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) {
+        self.changes.push(Change {
+            kind,
+            path: path.into(),
+            old: old.map(str::to_string),
+            new: new.map(str::to_string),
+        });
+    }
+
+    /// Single element, empty values are absent
+    fn field<P: Into<String>>(&mut self, path: P, old: Option<&str>, new: Option<&str>) {
+        let old = old.map(str::trim).filter(|value| !value.is_empty());
+        let new = new.map(str::trim).filter(|value| !value.is_empty());
+
+        match (old, new) {
+            (None, None) => {}
+            (Some(_), None) => self.push(ChangeKind::Removed, path, old, None),
+            (None, Some(_)) => self.push(ChangeKind::Added, path, None, new),
+            (Some(old), Some(new)) if old != new => {
+                self.push(ChangeKind::Modified, path, Some(old), Some(new))
+            }
+            _ => {}
+        }
+    }
+
+    /// Repeated element, matched by key. Identical entries are paired first, then the entries left with the same
+    /// key, so that a duplicated entry removed or added is reported.
+    fn keyed(&mut self, path: &str, old: &[Entry], new: &[Entry]) {
+        let path_of = |entry: &Entry| match &entry.label {
+            Some(label) => format!("{}[{}]", path, label),
+            None => path.to_string(),
+        };
+
+        let mut unmatched: Vec<Option<&Entry>> = new.iter().map(Some).collect();
+        let changed: Vec<&Entry> = old
+            .iter()
+            .filter(|entry| {
+                take(&mut unmatched, |other| {
+                    other.key == entry.key && other.value == entry.value
+                })
+                .is_none()
+            })
+            .collect();
+
+        for entry in changed {
+            match take(&mut unmatched, |other| other.key == entry.key) {
+                None => self.push(
+                    ChangeKind::Removed,
+                    path_of(entry),
+                    Some(&entry.value),
+                    None,
+                ),
+                Some(other) => self.push(
+                    ChangeKind::Modified,
+                    path_of(entry),
+                    Some(&entry.value),
+                    Some(&other.value),
+                ),
+            }
+        }
+
+        for entry in unmatched.into_iter().flatten() {
+            self.push(ChangeKind::Added, path_of(entry), None, Some(&entry.value));
+        }
+    }
+
+    fn thesis_record(&mut self, old: Option<&ThesisRecord>, new: Option<&ThesisRecord>) {
+        let path = "thesisRecord";
+        let (old, new) = match (old, new) {
+            (Some(old), Some(new)) => (old, new),
+            (old, new) => {
+                let title =
+                    |record: Option<&ThesisRecord>| record.map(|r| r.title().text().clone());
+                return self.field(path, title(old).as_deref(), title(new).as_deref());
+            }
+        };
+
+        self.field(
+            format!("{}/title", path),
+            Some(&titled(old.title().lang(), old.title().text())),
+            Some(&titled(new.title().lang(), new.title().text())),
+        );
+
+        let alternatives = |record: &ThesisRecord| -> Vec<Entry> {
+            record
+                .alternative()
+                .iter()
+                .flatten()
+                .map(|alternative| Entry::keyed(alternative.lang(), alternative.text()))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}/alternative", path),
+            &alternatives(old),
+            &alternatives(new),
+        );
+
+        let subjects = |record: &ThesisRecord| -> Vec<Entry> {
+            record
+                .subject()
+                .iter()
+                .flatten()
+                .map(|subject| {
+                    Entry::value(titled(
+                        subject.lang().as_deref().unwrap_or_default(),
+                        subject.text(),
+                    ))
+                })
+                .collect()
+        };
+        self.keyed(&format!("{}/subject", path), &subjects(old), &subjects(new));
+
+        let headings = |record: &ThesisRecord| -> Vec<Entry> {
+            record
+                .sujet_rameau()
+                .iter()
+                .flat_map(|sujet_rameau| sujet_rameau.values())
+                .flatten()
+                .map(|vedette| Entry::value(vedette.heading()))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}/sujetRameau", path),
+            &headings(old),
+            &headings(new),
+        );
+
+        let abstracts = |record: &ThesisRecord| -> Vec<Entry> {
+            record
+                .r#abstract()
+                .iter()
+                .flatten()
+                .map(|r#abstract| Entry::keyed(r#abstract.lang(), r#abstract.text()))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}/abstract", path),
+            &abstracts(old),
+            &abstracts(new),
+        );
+
+        let types = |record: &ThesisRecord| -> Vec<Entry> {
+            record
+                .r#type()
+                .iter()
+                .map(|r#type| Entry::value(r#type.text()))
+                .collect()
+        };
+        self.keyed(&format!("{}/type", path), &types(old), &types(new));
+
+        let languages = |record: &ThesisRecord| -> Vec<Entry> {
+            record
+                .language()
+                .iter()
+                .map(|language| Entry::value(language.text()))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}/language", path),
+            &languages(old),
+            &languages(new),
+        );
+
+        let coverages = |record: &ThesisRecord| -> Vec<Entry> {
+            record
+                .coverage()
+                .iter()
+                .flatten()
+                .map(|coverage| Entry::value(&coverage.0))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}/coverage", path),
+            &coverages(old),
+            &coverages(new),
+        );
+    }
+
+    fn thesis_admin(&mut self, old: Option<&ThesisAdmin>, new: Option<&ThesisAdmin>) {
+        let path = "thesisAdmin";
+        let (old, new) = match (old, new) {
+            (Some(old), Some(new)) => (old, new),
+            (old, new) => {
+                let auteur = |admin: Option<&ThesisAdmin>| admin.map(|a| person(a.auteur()));
+                return self.field(path, auteur(old).as_deref(), auteur(new).as_deref());
+            }
+        };
+
+        self.field(
+            format!("{}/auteur", path),
+            Some(&person(old.auteur())),
+            Some(&person(new.auteur())),
+        );
+        self.field(
+            format!("{}/auteur/dateNaissance", path),
+            old.auteur()
+                .date_naissance()
+                .as_ref()
+                .map(|date| date.0.as_str()),
+            new.auteur()
+                .date_naissance()
+                .as_ref()
+                .map(|date| date.0.as_str()),
+        );
+        self.field(
+            format!("{}/auteur/nationalite", path),
+            old.auteur().nationalite().as_deref(),
+            new.auteur().nationalite().as_deref(),
+        );
+
+        let identifiers = |admin: &ThesisAdmin| -> Vec<Entry> {
+            admin
+                .identifier()
+                .iter()
+                .map(|identifier| Entry::keyed(identifier.r#type(), identifier.text()))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}/identifier", path),
+            &identifiers(old),
+            &identifiers(new),
+        );
+
+        self.field(
+            format!("{}/dateAccepted", path),
+            Some(old.date_accepted().text()),
+            Some(new.date_accepted().text()),
+        );
+
+        let (old_degree, new_degree) = (old.thesis_degree(), new.thesis_degree());
+        let degree_path = format!("{}/thesis.degree", path);
+        self.field(
+            format!("{}/thesis.degree.discipline", degree_path),
+            Some(&titled(
+                old_degree.discipline().lang(),
+                old_degree.discipline().text(),
+            )),
+            Some(&titled(
+                new_degree.discipline().lang(),
+                new_degree.discipline().text(),
+            )),
+        );
+        self.keyed(
+            &format!("{}/thesis.degree.grantor", degree_path),
+            &organizations(old_degree.grantor()),
+            &organizations(new_degree.grantor()),
+        );
+        self.field(
+            format!("{}/thesis.degree.level", degree_path),
+            Some(old_degree.level().text().as_str()),
+            Some(new_degree.level().text().as_str()),
+        );
+        self.field(
+            format!("{}/thesis.degree.name", degree_path),
+            old_degree.name().as_ref().map(|name| name.0.as_str()),
+            new_degree.name().as_ref().map(|name| name.0.as_str()),
+        );
+
+        self.field(
+            format!("{}/theseSurTravaux", path),
+            Some(&old.these_sur_travaux().0),
+            Some(&new.these_sur_travaux().0),
+        );
+        self.field(
+            format!("{}/avisJury", path),
+            Some(&old.avis_jury().0),
+            Some(&new.avis_jury().0),
+        );
+
+        self.keyed(
+            &format!("{}/directeurThese", path),
+            &persons(old.directeur_these()),
+            &persons(new.directeur_these()),
+        );
+        self.keyed(
+            &format!("{}/presidentJury", path),
+            &persons(old.president_jury().as_slice()),
+            &persons(new.president_jury().as_slice()),
+        );
+        self.keyed(
+            &format!("{}/membreJury", path),
+            &persons(old.membre_jury()),
+            &persons(new.membre_jury()),
+        );
+        self.keyed(
+            &format!("{}/rapporteur", path),
+            &persons(old.rapporteur()),
+            &persons(new.rapporteur()),
+        );
+        self.keyed(
+            &format!("{}/ecoleDoctorale", path),
+            &organizations(old.ecole_doctorale()),
+            &organizations(new.ecole_doctorale()),
+        );
+        self.keyed(
+            &format!("{}/partenaireRecherche", path),
+            &organizations(old.partenaire_recherche()),
+            &organizations(new.partenaire_recherche()),
+        );
+
+        let set_specs = |admin: &ThesisAdmin| -> Vec<Entry> {
+            admin
+                .oai_set_spec()
+                .iter()
+                .map(|set_spec| Entry::value(&set_spec.0))
+                .collect()
+        };
+        self.keyed(
+            &format!("{}/oaiSetSpec", path),
+            &set_specs(old),
+            &set_specs(new),
+        );
+    }
+
+    #[cfg(feature = "gestion")]
+    fn star_gestion(&mut self, old: Option<&StarGestion>, new: Option<&StarGestion>) {
+        let path = "starGestion/traitements/sorties/diffusion";
+        let old = old.map(|star_gestion| star_gestion.traitements().sorties().diffusion());
+        let new = new.map(|star_gestion| star_gestion.traitements().sorties().diffusion());
+
+        let fields: [(&str, DiffusionField); 6] = [
+            ("urlPerenne", |diffusion| diffusion.url_perenne()),
+            ("typeDiffusion", |diffusion| diffusion.type_diffusion()),
+            ("restrictionTemporelleType", |diffusion| {
+                diffusion.restriction_temporelle_type()
+            }),
+            ("restrictionTemporelleFin", |diffusion| {
+                diffusion.restriction_temporelle_fin()
+            }),
+            ("embargoFin", |diffusion| diffusion.embargo_fin()),
+            ("confidentialiteFin", |diffusion| {
+                diffusion.confidentialite_fin()
+            }),
+        ];
+
+        for (name, field) in fields {
+            self.field(
+                format!("{}/@{}", path, name),
+                old.map(|diffusion| field(diffusion).as_str()),
+                new.map(|diffusion| field(diffusion).as_str()),
+            );
+        }
+    }
+}
+
+/// Text with its language, e.g. `Matériaux (fr)`
+fn titled(lang: &str, text: &str) -> String {
+    match lang.trim() {
+        "" => text.trim().to_string(),
+        lang => format!("{} ({})", text.trim(), lang),
+    }
+}
+
+/// Name of the element and its external authorities, e.g. `Jean Dupont (sudoc:123456789)`
+fn described<A: Authorities + ?Sized>(name: String, element: &A) -> String {
+    let autorites: Vec<String> = element
+        .autorites_externes()
+        .iter()
+        .map(|autorite| {
+            format!(
+                "{}:{}",
+                autorite.autorite_source().trim(),
+                autorite.text().trim()
+            )
+        })
+        .collect();
+
+    match autorites.is_empty() {
+        true => name,
+        false => format!("{} ({})", name, autorites.join(", ")),
+    }
+}
+
+fn person<P: Person>(person: &P) -> String {
+    described(person.display_name(), person)
+}
+
+/// Persons matched by [`Person::matching_key`]
+fn persons<P: Person>(persons: &[P]) -> Vec<Entry> {
+    persons
+        .iter()
+        .map(|element| Entry {
+            key: element.matching_key(),
+            label: Some(element.display_name()),
+            value: person(element),
+        })
+        .collect()
+}
+
+/// Organizations matched by their folded name
+fn organizations<O: Organization>(organizations: &[O]) -> Vec<Entry> {
+    organizations
+        .iter()
+        .map(|organization| Entry {
+            key: crate::names::matching_key(&organization.nom().0, ""),
+            label: Some(organization.nom().0.trim().to_string()),
+            value: described(organization.nom().0.trim().to_string(), organization),
+        })
+        .collect()
+}
+
+/// First entry left that matches, it is no longer available
+fn take<'e>(
+    entries: &mut [Option<&'e Entry>],
+    matches: impl Fn(&Entry) -> bool,
+) -> Option<&'e Entry> {
+    entries
+        .iter_mut()
+        .find(|entry| entry.is_some_and(&matches))
+        .and_then(Option::take)
+}
+
+/// Edition keyed by the `ID` of its `mets:dmdSec`, with its identifiers, dates, media, extents and publishers
+fn edition((id, edition): &(&str, &Edition)) -> Entry {
+    let values: Vec<String> = edition
+        .values()
+        .iter()
+        .filter_map(|value| match value {
+            EditionValues::Medium(medium) => Some(format!("medium={}", medium.text().trim())),
+            EditionValues::Extent(extent) => Some(format!("extent={}", extent.0.trim())),
+            EditionValues::Identifier(identifier) => {
+                Some(format!("identifier={}", identifier.text().trim()))
+            }
+            EditionValues::Issued(issued) => Some(format!("issued={}", issued.text().trim())),
+            EditionValues::Replaces(replaces) => {
+                Some(format!("replaces={}", replaces.text().trim()))
+            }
+            EditionValues::Editeur(editeur) => Some(format!(
+                "editeur={}",
+                described(editeur.nom().0.trim().to_string(), editeur)
+            )),
+            EditionValues::Other(_) => None,
+        })
+        .collect();
+
+    Entry::keyed(*id, values.join("; "))
+}
+
+/// Rights declarations keyed by the `ID` of their `mets:rightsMD`
+fn rights((id, rights): &(&str, &RightsDeclarationMD)) -> Entry {
+    let values: Vec<String> = rights
+        .values()
+        .iter()
+        .map(|value| match value {
+            RightsDeclarationMDValues::RightsDeclaration(declaration) => {
+                declaration.0.trim().to_string()
+            }
+            RightsDeclarationMDValues::Context(context) => {
+                format!("context={}", context.contextclass().trim())
+            }
+            RightsDeclarationMDValues::RightsHolder(holder) => format!(
+                "holder={}",
+                holder
+                    .0
+                    .iter()
+                    .map(|name| name.0.trim())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ),
+        })
+        .collect();
+
+    Entry::keyed(*id, values.join("; "))
+}
+
+/// File keyed by its `ID`, with its location, type and use
+fn file_entry(file: &File) -> Entry {
+    let mut value = file.value().href().trim().to_string();
+    if let Some(mimetype) = file.mimetype() {
+        value = format!("{} ({})", value, mimetype.trim());
+    }
+    if let Some(r#use) = file.r#use() {
+        value = format!("{} [{}]", value, r#use.trim());
+    }
+
+    Entry::keyed(file.id(), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::THESE;
+
+    #[test]
+    fn duplicated_subject() {
+        let old = THESE.replace(
+            r#"<dc:subject xml:lang="en">Materials</dc:subject>"#,
+            r#"<dc:subject xml:lang="en">Materials</dc:subject>
+          <dc:subject xml:lang="en">Materials</dc:subject>"#,
+        );
+        let changes = diff(&parse_tef(&old).unwrap(), &parse_tef(THESE).unwrap());
+
+        assert_eq!(
+            to_text(&changes),
+            "- thesisRecord/subject: Materials (en)\n"
+        );
+    }
+
+    #[test]
+    fn thesis_record_of_the_struct_map() {
+        let start = THESE.find(r#"<mets:dmdSec ID="desc_these">"#).unwrap();
+        let end = THESE.find(r#"<mets:dmdSec ID="desc_edition">"#).unwrap();
+        let other = THESE[start..end]
+            .replace("desc_these", "desc_autre")
+            .replace("Étude des propriétés des matériaux", "Autre thèse");
+        let new = format!("{}{}{}", &THESE[..start], other, &THESE[start..]);
+
+        assert!(diff(&parse_tef(THESE).unwrap(), &parse_tef(&new).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn jury() {
+        let new = THESE.replace(
+            "<tef:nom>Durand</tef:nom>\n              <tef:prenom>Anne</tef:prenom>",
+            "<tef:nom>Petit</tef:nom>\n              <tef:prenom>Claire</tef:prenom>",
+        );
+        let changes = diff(&parse_tef(THESE).unwrap(), &parse_tef(&new).unwrap());
+
+        assert_eq!(
+            to_text(&changes),
+            "- thesisAdmin/membreJury[Anne Durand]: Anne Durand\n\
+             + thesisAdmin/membreJury[Claire Petit]: Claire Petit\n"
+        );
+
+        // the same person written another way
+        let new = THESE.replace("<tef:nom>Durand</tef:nom>", "<tef:nom>DURAND</tef:nom>");
+        let changes = diff(&parse_tef(THESE).unwrap(), &parse_tef(&new).unwrap());
+
+        assert_eq!(
+            to_text(&changes),
+            "~ thesisAdmin/membreJury[Anne Durand]: Anne Durand -> Anne DURAND\n"
+        );
+    }
+
+    #[test]
+    fn r#abstract() {
+        let new = THESE.replace("English abstract.", "New English abstract.");
+        let changes = diff(&parse_tef(THESE).unwrap(), &parse_tef(&new).unwrap());
+
+        assert_eq!(
+            to_text(&changes),
+            "~ thesisRecord/abstract[en]: English abstract. -> New English abstract.\n"
+        );
+        assert_eq!(
+            to_json(&changes).unwrap(),
+            r#"[
+  {
+    "kind": "modified",
+    "path": "thesisRecord/abstract[en]",
+    "old": "English abstract.",
+    "new": "New English abstract."
+  }
+]"#
+        );
+    }
+
+    #[cfg(feature = "gestion")]
+    #[test]
+    fn embargo() {
+        use crate::fixtures::these_with;
+
+        let with_embargo = |embargo_fin: &str| {
+            let input = these_with(&[(
+                "</tef:thesisAdmin>",
+                r#"<tef:vie derogationDixAns="non">
+                    <tef:soutenancePrevue><tef:datePrevue>2019-10-21</tef:datePrevue></tef:soutenancePrevue>
+                  </tef:vie>
+                </tef:thesisAdmin>"#,
+            )]);
+            let mut mets = parse_tef(&input).unwrap();
+
+            let mut star_gestion = StarGestion::default();
+            star_gestion
+                .traitements_mut()
+                .sorties_mut()
+                .diffusion_mut()
+                .set_embargo_fin(embargo_fin.to_string());
+            let mut tech_md = TechMD::default();
+            tech_md.set_id("star_gestion".to_string());
+            tech_md
+                .value_mut()
+                .value_mut()
+                .set_value(XmlDataValues::StarGestion(star_gestion));
+
+            for value in mets.values_mut() {
+                if let MetsValues::AmdSec(amd_sec) = value {
+                    amd_sec.values_mut().push(AmdSecValues::TechMD(tech_md));
+                    break;
+                }
+            }
+            mets
+        };
+
+        let changes = diff(&with_embargo("2021-01-01"), &with_embargo("2022-06-30"));
+        assert_eq!(
+            to_text(&changes),
+            "~ starGestion/traitements/sorties/diffusion/@embargoFin: 2021-01-01 -> 2022-06-30\n"
+        );
+    }
+}
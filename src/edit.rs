@@ -0,0 +1,417 @@
+use crate::dc::*;
+use crate::dcterms::*;
+use crate::error::Error;
+use crate::identifiers::{Ppn, PPN_SOURCES};
+use crate::mets::*;
+use crate::tef::*;
+use crate::thesis::Thesis;
+
+/// Path of the `tef:thesisAdmin`, in errors
+pub(crate) const THESIS_ADMIN: &str = "mets/amdSec/techMD/mdWrap/xmlData/thesisAdmin";
+/// Path of the `tef:thesisRecord`, in errors
+pub(crate) const THESIS_RECORD: &str = "mets/dmdSec/mdWrap/xmlData/thesisRecord";
+
+impl Mets {
+    /// Descriptive metadata of the thesis: the `tef:thesisRecord` the thesis div points to, or else the first one
+    pub fn thesis_record(&self) -> Option<&ThesisRecord> {
+        let (dmdids, _) = thesis_ids(self);
+
+        let records: Vec<(bool, &ThesisRecord)> = self
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                MetsValues::DmdSec(dmd_sec) => match dmd_sec.value().value().value() {
+                    XmlDataValues::ThesisRecord(record) => {
+                        Some((dmdids.contains(dmd_sec.id()), record))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        first_referenced(records)
+    }
+
+    /// Administrative metadata of the thesis: the `tef:thesisAdmin` the thesis div points to, or else the first one
+    pub fn thesis_admin(&self) -> Option<&ThesisAdmin> {
+        let (_, admids) = thesis_ids(self);
+
+        let admins: Vec<(bool, &ThesisAdmin)> = self
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                MetsValues::AmdSec(amd_sec) => Some(amd_sec.values()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|value| match value {
+                AmdSecValues::TechMD(tech_md) => match tech_md.value().value().value() {
+                    XmlDataValues::ThesisAdmin(admin) => {
+                        Some((admids.contains(tech_md.id()), admin))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        first_referenced(admins)
+    }
+
+    /// Mutable access to the descriptive metadata of the thesis, see [`Mets::thesis_record`]
+    pub fn thesis_record_mut(&mut self) -> Option<&mut ThesisRecord> {
+        let (dmdids, _) = thesis_ids(self);
+
+        let records: Vec<(bool, &mut ThesisRecord)> = self
+            .values_mut()
+            .iter_mut()
+            .filter_map(|value| match value {
+                MetsValues::DmdSec(dmd_sec) => {
+                    let referenced = dmdids.contains(dmd_sec.id());
+                    match dmd_sec.value_mut().value_mut().value_mut() {
+                        XmlDataValues::ThesisRecord(record) => Some((referenced, record)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+
+        first_referenced(records)
+    }
+
+    /// Mutable access to the administrative metadata of the thesis, see [`Mets::thesis_admin`]
+    pub fn thesis_admin_mut(&mut self) -> Option<&mut ThesisAdmin> {
+        let (_, admids) = thesis_ids(self);
+
+        let admins: Vec<(bool, &mut ThesisAdmin)> = self
+            .values_mut()
+            .iter_mut()
+            .filter_map(|value| match value {
+                MetsValues::AmdSec(amd_sec) => Some(amd_sec.values_mut()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|value| match value {
+                AmdSecValues::TechMD(tech_md) => {
+                    let referenced = admids.contains(tech_md.id());
+                    match tech_md.value_mut().value_mut().value_mut() {
+                        XmlDataValues::ThesisAdmin(admin) => Some((referenced, admin)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+
+        first_referenced(admins)
+    }
+
+    /// Replace the title of the thesis
+    pub fn set_title<L: Into<String>, T: Into<String>>(
+        &mut self,
+        lang: L,
+        text: T,
+    ) -> Result<(), Error> {
+        self.record_mut()?.set_title(Title::new(lang, text));
+        Ok(())
+    }
+
+    /// Add a free subject, unless the same subject is already there
+    pub fn add_subject<L: Into<String>, T: Into<String>>(
+        &mut self,
+        lang: L,
+        text: T,
+    ) -> Result<(), Error> {
+        let subject = Subject::new(lang, text);
+        let subjects = self
+            .record_mut()?
+            .subject_mut()
+            .get_or_insert_with(Vec::new);
+
+        if !subjects.contains(&subject) {
+            subjects.push(subject);
+        }
+        Ok(())
+    }
+
+    /// Replace the abstract in the given language, or add it when there is none
+    pub fn set_abstract<L: Into<String>, T: Into<String>>(
+        &mut self,
+        lang: L,
+        text: T,
+    ) -> Result<(), Error> {
+        let r#abstract = Abstract::new(lang, text);
+        let abstracts = self
+            .record_mut()?
+            .r#abstract_mut()
+            .get_or_insert_with(Vec::new);
+
+        match abstracts
+            .iter_mut()
+            .find(|existing| existing.lang() == r#abstract.lang())
+        {
+            Some(existing) => *existing = r#abstract,
+            None => abstracts.push(r#abstract),
+        }
+        Ok(())
+    }
+
+    /// Add an OAI set, unless it is already there
+    pub fn add_oai_set_spec<T: Into<String>>(&mut self, set_spec: T) -> Result<(), Error> {
+        let set_spec = OaiSetSpec(set_spec.into());
+        let set_specs = self.admin_mut()?.oai_set_spec_mut();
+
+        if !set_specs.contains(&set_spec) {
+            set_specs.push(set_spec);
+        }
+        Ok(())
+    }
+
+    /// Replace a PPN by another one in the external authorities of the persons and organizations of the thesis,
+    /// of the publishers of its editions and in its Rameau headings, returns the number of replaced identifiers.
+    ///
+    /// Only the authorities of a PPN source (see [`PPN_SOURCES`]) are considered. Identifiers are compared as PPN,
+    /// whatever their case or form, and the new one is written as a bare PPN.
+    pub fn replace_authority(&mut self, old: &Ppn, new: &Ppn) -> usize {
+        let mut count = 0;
+
+        if let Some(admin) = self.thesis_admin_mut() {
+            count += replace_autorites(admin.auteur_mut().autorite_externe_mut(), old, new);
+            for grantor in admin.thesis_degree_mut().grantor_mut() {
+                count += replace_autorites(grantor.autorite_externe_mut(), old, new);
+            }
+            for person in admin.directeur_these_mut() {
+                count += replace_autorites(person.autorite_externe_mut(), old, new);
+            }
+            if let Some(person) = admin.president_jury_mut() {
+                count += replace_autorites(person.autorite_externe_mut(), old, new);
+            }
+            for person in admin.membre_jury_mut() {
+                count += replace_autorites(person.autorite_externe_mut(), old, new);
+            }
+            for person in admin.rapporteur_mut() {
+                count += replace_autorites(person.autorite_externe_mut(), old, new);
+            }
+            for ecole in admin.ecole_doctorale_mut() {
+                count += replace_autorites(ecole.autorite_externe_mut(), old, new);
+            }
+            for partenaire in admin.partenaire_recherche_mut() {
+                count += replace_autorites(partenaire.autorite_externe_mut(), old, new);
+            }
+        }
+
+        if let Some(record) = self.thesis_record_mut() {
+            let vedettes = record
+                .sujet_rameau_mut()
+                .iter_mut()
+                .flat_map(|sujet_rameau| sujet_rameau.values_mut())
+                .flatten();
+
+            for vedette in vedettes {
+                let entree = vedette.element_entree_mut();
+                let source = entree.autorite_source().clone();
+                count += replace_id(source.as_deref(), entree.autorite_externe_mut(), old, new);
+                for subdivision in vedette.subdivisions_mut().iter_mut().flatten() {
+                    let source = subdivision.autorite_source().clone();
+                    count += replace_id(
+                        source.as_deref(),
+                        subdivision.autorite_externe_mut(),
+                        old,
+                        new,
+                    );
+                }
+            }
+        }
+
+        let editions = self
+            .values_mut()
+            .iter_mut()
+            .filter_map(|value| match value {
+                MetsValues::DmdSec(dmd_sec) => match dmd_sec.value_mut().value_mut().value_mut() {
+                    XmlDataValues::Edition(edition) => Some(edition),
+                    _ => None,
+                },
+                _ => None,
+            });
+        for edition in editions {
+            for value in edition.values_mut() {
+                if let EditionValues::Editeur(editeur) = value {
+                    count += replace_autorites(editeur.autorite_externe_mut(), old, new);
+                }
+            }
+        }
+
+        count
+    }
+
+    fn record_mut(&mut self) -> Result<&mut ThesisRecord, Error> {
+        self.thesis_record_mut()
+            .ok_or_else(|| Error::missing(THESIS_RECORD))
+    }
+
+    fn admin_mut(&mut self) -> Result<&mut ThesisAdmin, Error> {
+        self.thesis_admin_mut()
+            .ok_or_else(|| Error::missing(THESIS_ADMIN))
+    }
+}
+
+/// `ID` of the descriptive and administrative sections the thesis div points to
+fn thesis_ids(mets: &Mets) -> (Vec<String>, Vec<String>) {
+    match Thesis::from_mets(mets) {
+        Ok(thesis) => {
+            let metadata = thesis.entity().metadata();
+            (
+                metadata
+                    .descriptive()
+                    .iter()
+                    .map(|dmd_sec| dmd_sec.id().clone())
+                    .collect(),
+                metadata
+                    .administrative()
+                    .iter()
+                    .map(|tech_md| tech_md.id().clone())
+                    .collect(),
+            )
+        }
+        Err(_) => (vec![], vec![]),
+    }
+}
+
+fn first_referenced<T>(values: Vec<(bool, T)>) -> Option<T> {
+    let index = values
+        .iter()
+        .position(|(referenced, _)| *referenced)
+        .unwrap_or_default();
+
+    values.into_iter().nth(index).map(|(_, value)| value)
+}
+
+fn replace_autorites(autorites: &mut Option<Vec<AutoriteExterne>>, old: &Ppn, new: &Ppn) -> usize {
+    autorites
+        .iter_mut()
+        .flatten()
+        .filter(|autorite| is_ppn_source(autorite.autorite_source()))
+        .map(|autorite| replace(autorite.text_mut(), old, new))
+        .sum()
+}
+
+fn replace_id(source: Option<&str>, id: &mut Option<String>, old: &Ppn, new: &Ppn) -> usize {
+    match source {
+        Some(source) if is_ppn_source(source) => id.as_mut().map_or(0, |id| replace(id, old, new)),
+        _ => 0,
+    }
+}
+
+fn is_ppn_source(source: &str) -> bool {
+    PPN_SOURCES.contains(&source.trim().to_lowercase().as_str())
+}
+
+fn replace(id: &mut String, old: &Ppn, new: &Ppn) -> usize {
+    match id.parse::<Ppn>() {
+        Ok(ppn) if &ppn == old => {
+            *id = new.to_string();
+            1
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::{these_with_editeur, THESE};
+
+    #[test]
+    fn set_title() {
+        let mut mets = parse_tef(THESE).unwrap();
+        mets.set_title("en", "Study of material properties")
+            .unwrap();
+
+        let title = mets.thesis_record().unwrap().title();
+        assert_eq!(title.lang(), "en");
+        assert_eq!(title.text(), "Study of material properties");
+    }
+
+    #[test]
+    fn add_subject() {
+        let mut mets = parse_tef(THESE).unwrap();
+        mets.add_subject("fr", "Matériaux").unwrap();
+        mets.add_subject("fr", "Physique").unwrap();
+        mets.add_subject("fr", "Physique").unwrap();
+
+        let subjects = mets.thesis_record().unwrap().subject().as_ref().unwrap();
+        let subjects: Vec<(Option<&str>, &str)> = subjects
+            .iter()
+            .map(|subject| (subject.lang().as_deref(), subject.text().as_str()))
+            .collect();
+        assert_eq!(
+            subjects,
+            [
+                (Some("fr"), "Matériaux"),
+                (Some("en"), "Materials"),
+                (Some("fr"), "Physique")
+            ]
+        );
+    }
+
+    #[test]
+    fn set_abstract() {
+        let mut mets = parse_tef(THESE).unwrap();
+        mets.set_abstract("en", "New abstract.").unwrap();
+        mets.set_abstract("de", "Zusammenfassung.").unwrap();
+
+        let abstracts = mets.thesis_record().unwrap().r#abstract().as_ref().unwrap();
+        let abstracts: Vec<(&str, &str)> = abstracts
+            .iter()
+            .map(|r#abstract| (r#abstract.lang().as_str(), r#abstract.text().as_str()))
+            .collect();
+        assert_eq!(
+            abstracts,
+            [
+                ("fr", "Résumé en français."),
+                ("en", "New abstract."),
+                ("de", "Zusammenfassung.")
+            ]
+        );
+    }
+
+    #[test]
+    fn add_oai_set_spec() {
+        let mut mets = parse_tef(THESE).unwrap();
+        mets.add_oai_set_spec("ddc:530").unwrap();
+        mets.add_oai_set_spec("ddc:540").unwrap();
+
+        let set_specs: Vec<&str> = mets
+            .thesis_admin()
+            .unwrap()
+            .oai_set_spec()
+            .iter()
+            .map(|set_spec| set_spec.0.as_str())
+            .collect();
+        assert_eq!(set_specs, ["ddc:530", "ddc:540"]);
+    }
+
+    #[test]
+    fn replace_authority() {
+        let input = these_with_editeur(
+            r#"<tef:nom>Université de Lyon</tef:nom>
+                <tef:place>Lyon</tef:place>
+                <tef:autoriteExterne autoriteSource="idref">137964781</tef:autoriteExterne>
+                <tef:autoriteExterne autoriteSource="viaf">137964781</tef:autoriteExterne>"#,
+        );
+        let mut mets = parse_tef(&input).unwrap();
+        let old: Ppn = "137964781".parse().unwrap();
+        let new: Ppn = "026403447".parse().unwrap();
+
+        // the grantor and the publisher, not the VIAF identifier
+        assert_eq!(mets.replace_authority(&old, &new), 2);
+
+        let output = crate::to_tef_string(&mets).unwrap();
+        assert_eq!(output.matches("026403447").count(), 2);
+        assert!(output.contains(r#"autoriteSource="viaf">137964781<"#));
+    }
+}
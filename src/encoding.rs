@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+
+use crate::error::Error;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// Encodings supported when reading raw bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    Utf8,
+    Latin1,
+}
+
+impl Encoding {
+    fn from_label(label: &str) -> Result<Self, Error> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" | "us-ascii" | "ascii" => Ok(Encoding::Utf8),
+            "iso-8859-1" | "iso8859-1" | "iso_8859-1" | "latin1" | "latin-1" | "l1" => {
+                Ok(Encoding::Latin1)
+            }
+            _ => Err(Error::UnsupportedEncoding(label.to_string())),
+        }
+    }
+}
+
+/// Find the encoding declared in the XML declaration (`<?xml ... encoding="..."?>`).
+///
+/// The declaration is ASCII only, it can be read before knowing the encoding.
+fn declared_encoding(input: &[u8]) -> Option<&str> {
+    if !input.starts_with(b"<?xml") {
+        return None;
+    }
+
+    let end = input.windows(2).position(|w| w == b"?>")?;
+    let decl = std::str::from_utf8(&input[..end]).ok()?;
+    let rest = &decl[decl.find("encoding")? + "encoding".len()..];
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let rest = &rest[1..];
+
+    Some(&rest[..rest.find(quote)?])
+}
+
+/// Detect the encoding of a document using its BOM and XML declaration.
+pub(crate) fn detect(input: &[u8]) -> Result<Encoding, Error> {
+    if input.starts_with(UTF8_BOM) {
+        return Ok(Encoding::Utf8);
+    }
+
+    if input.starts_with(&[0xFF, 0xFE]) || input.starts_with(&[0xFE, 0xFF]) {
+        return Err(Error::UnsupportedEncoding("UTF-16".to_string()));
+    }
+
+    match declared_encoding(input) {
+        Some(label) => Encoding::from_label(label),
+        None => Ok(Encoding::Utf8),
+    }
+}
+
+/// Decode raw bytes to a string, following the document encoding.
+pub(crate) fn decode(input: &[u8]) -> Result<Cow<'_, str>, Error> {
+    match detect(input)? {
+        Encoding::Utf8 => {
+            let content = input.strip_prefix(UTF8_BOM).unwrap_or(input);
+
+            std::str::from_utf8(content)
+                .map(Cow::Borrowed)
+                .map_err(|e| Error::InvalidEncoding {
+                    encoding: "UTF-8",
+                    offset: input.len() - content.len() + e.valid_up_to(),
+                })
+        }
+        // ISO-8859-1 code points are the first 256 unicode code points
+        Encoding::Latin1 => Ok(Cow::Owned(input.iter().map(|b| *b as char).collect())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::THESE;
+
+    #[test]
+    fn latin1() {
+        let input = b"<?xml version='1.0' encoding='ISO-8859-1'?><t>\xC9tude \xE0 Lyon</t>";
+
+        assert_eq!(detect(input).unwrap(), Encoding::Latin1);
+        assert_eq!(
+            decode(input).unwrap(),
+            "<?xml version='1.0' encoding='ISO-8859-1'?><t>Étude à Lyon</t>"
+        );
+    }
+
+    #[test]
+    fn bom() {
+        let input = b"\xEF\xBB\xBF<?xml version=\"1.0\"?><t>\xC3\xA9</t>";
+
+        assert_eq!(detect(input).unwrap(), Encoding::Utf8);
+        assert_eq!(decode(input).unwrap(), "<?xml version=\"1.0\"?><t>é</t>");
+        assert!(matches!(
+            decode(b"\xEF\xBB\xBF<t>\xE9</t>"),
+            Err(Error::InvalidEncoding {
+                encoding: "UTF-8",
+                offset: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(matches!(
+            detect(b"\xFF\xFE<\x00t\x00>\x00"),
+            Err(Error::UnsupportedEncoding(label)) if label == "UTF-16"
+        ));
+        assert!(matches!(
+            detect(b"<?xml version=\"1.0\" encoding=\"Shift_JIS\"?><t/>"),
+            Err(Error::UnsupportedEncoding(label)) if label == "Shift_JIS"
+        ));
+    }
+
+    #[test]
+    fn latin1_record() {
+        let latin1: Vec<u8> = THESE
+            .replacen("UTF-8", "ISO-8859-1", 1)
+            .chars()
+            .map(|c| u8::try_from(c).unwrap())
+            .collect();
+        let mets = crate::parse_tef_from_slice(&latin1).unwrap();
+        let record = mets.thesis_record().unwrap();
+
+        assert_eq!(record.title().text(), "Étude des propriétés des matériaux");
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Display;
+
+use getset::Getters;
+use thiserror::Error;
+
+/// Position of an element in a TEF document
+///
+/// The path is made of local names, with the position of the element among its siblings of the same name when
+/// there is more than one, e.g. `mets/dmdSec[2]/mdWrap/xmlData/thesisRecord/title`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct Location {
+    path: String,
+    offset: Option<usize>,
+}
+
+impl Location {
+    pub fn new<S: Into<String>>(path: S, offset: Option<usize>) -> Self {
+        Self {
+            path: path.into(),
+            offset,
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.path.is_empty(), self.offset) {
+            (false, Some(offset)) => write!(f, "{} (byte {})", self.path, offset),
+            (false, None) => write!(f, "{}", self.path),
+            (true, Some(offset)) => write!(f, "byte {}", offset),
+            (true, None) => write!(f, "unknown location"),
+        }
+    }
+}
+
+/// Errors returned by the crate
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("unsupported encoding: {0}")]
+    UnsupportedEncoding(String),
+    #[error("invalid {encoding} input at byte {offset}")]
+    InvalidEncoding {
+        encoding: &'static str,
+        offset: usize,
+    },
+    #[error("xml syntax error at {location}: {source}")]
+    Syntax {
+        #[source]
+        source: quick_xml::Error,
+        location: Location,
+    },
+    #[error("deserialization error at {location}: {source}")]
+    Deserialize {
+        #[source]
+        source: quick_xml::DeError,
+        location: Location,
+    },
+    #[error("missing element {path}")]
+    MissingElement { path: String },
+    #[error("invalid date \"{value}\": {reason}")]
+    InvalidDate { value: String, reason: &'static str },
+    #[error("invalid {kind} \"{value}\": {reason}")]
+    InvalidIdentifier {
+        kind: &'static str,
+        value: String,
+        reason: &'static str,
+    },
+    #[error("invalid element {path}: {message}")]
+    Validation { path: String, message: String },
+    #[error("record {identifier}: {source}")]
+    Record {
+        identifier: String,
+        #[source]
+        source: Box<Error>,
+    },
+    #[error("OAI-PMH error {code}: {message}")]
+    OaiPmh { code: String, message: String },
+    #[error("{format} export: {reason}")]
+    Export {
+        format: &'static str,
+        reason: String,
+    },
+}
+
+impl Error {
+    /// Error for a mandatory element absent from the document
+    pub fn missing<S: Into<String>>(path: S) -> Self {
+        Error::MissingElement { path: path.into() }
+    }
+
+    /// Error for an element breaking a rule of the TEF profile
+    pub fn invalid<P: Into<String>, M: Into<String>>(path: P, message: M) -> Self {
+        Error::Validation {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Location of the error in the document, when known
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Syntax { location, .. } | Error::Deserialize { location, .. } => Some(location),
+            Error::Record { source, .. } => source.location(),
+            _ => None,
+        }
+    }
+}
+
+impl From<quick_xml::Error> for Error {
+    fn from(source: quick_xml::Error) -> Self {
+        Error::Syntax {
+            source,
+            location: Location::default(),
+        }
+    }
+}
+
+impl From<quick_xml::events::attributes::AttrError> for Error {
+    fn from(source: quick_xml::events::attributes::AttrError) -> Self {
+        Error::from(quick_xml::Error::InvalidAttr(source))
+    }
+}
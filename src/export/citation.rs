@@ -0,0 +1,409 @@
+//! Citations of a thesis: BibTeX `@phdthesis` entries, RIS `THES` records and CSL-JSON `thesis` items
+
+use serde_json::{json, Map, Value};
+
+use super::{edition_urls, metadata, thesis_type};
+use crate::error::Error;
+use crate::identifiers::Nnt;
+use crate::mets::Mets;
+use crate::names::{matching_key, sort_name, PARTICLES};
+use crate::tef::*;
+
+/// Convert a TEF record to a BibTeX `@phdthesis` entry.
+///
+/// The key is the NNT, the author is written `Nom, Prénom` and the grantors are the school. Special characters of
+/// LaTeX are escaped, accented letters are kept as UTF-8.
+pub fn to_bibtex(mets: &Mets) -> Result<String, Error> {
+    let (record, admin) = metadata(mets)?;
+    let citation = Citation::new(mets, record, admin);
+
+    let mut fields: Vec<(&str, String)> = vec![
+        ("author", bibtex_name(admin.auteur())),
+        // double braces keep the case of the title
+        ("title", format!("{{{}}}", escape_bibtex(&citation.title))),
+        ("school", escape_bibtex(&citation.grantors.join("; "))),
+    ];
+    if let Some(year) = citation.year {
+        fields.push(("year", year.to_string()));
+    }
+    fields.push(("type", escape_bibtex(citation.r#type)));
+    if let Some(language) = citation.languages.first() {
+        fields.push(("language", escape_bibtex(language)));
+    }
+    if !citation.keywords.is_empty() {
+        fields.push(("keywords", escape_bibtex(&citation.keywords.join(", "))));
+    }
+    if let Some(r#abstract) = citation.r#abstract {
+        fields.push(("abstract", escape_bibtex(r#abstract)));
+    }
+    if let Some(url) = citation.urls.first() {
+        // the url package reads the URL verbatim
+        fields.push(("url", url.replace(['{', '}'], "")));
+    }
+    if let Some(nnt) = &citation.nnt {
+        fields.push(("note", format!("NNT : {}", nnt)));
+    }
+
+    let mut entry = format!("@phdthesis{{{},\n", citation.key());
+    for (name, value) in fields.iter().filter(|(_, value)| !value.is_empty()) {
+        entry.push_str(&format!("  {} = {{{}}},\n", name, value));
+    }
+    entry.push_str("}\n");
+
+    Ok(entry)
+}
+
+/// Convert a TEF record to a RIS `THES` record.
+///
+/// Persons are written `Nom, Prénom`, the author as `AU` and the directors as `A3`, the grantors are the
+/// publisher. Values are kept on a single line and lines end with CRLF.
+pub fn to_ris(mets: &Mets) -> Result<String, Error> {
+    let (record, admin) = metadata(mets)?;
+    let citation = Citation::new(mets, record, admin);
+
+    let mut tags: Vec<(&str, String)> = vec![
+        ("TY", "THES".to_string()),
+        ("AU", admin.auteur().sort_name()),
+    ];
+    tags.extend(
+        admin
+            .directeur_these()
+            .iter()
+            .map(|directeur| ("A3", directeur.sort_name())),
+    );
+    tags.push(("TI", citation.title.clone()));
+    if let Some(year) = citation.year {
+        tags.push(("PY", year.to_string()));
+    }
+    if let Some(date) = citation.date {
+        tags.push(("DA", date.replace('-', "/")));
+    }
+    tags.extend(
+        citation
+            .grantors
+            .iter()
+            .map(|grantor| ("PB", grantor.clone())),
+    );
+    tags.push(("M3", citation.r#type.to_string()));
+    tags.extend(
+        citation
+            .languages
+            .iter()
+            .map(|language| ("LA", language.clone())),
+    );
+    if let Some(r#abstract) = citation.r#abstract {
+        tags.push(("AB", r#abstract.to_string()));
+    }
+    tags.extend(
+        citation
+            .keywords
+            .iter()
+            .map(|keyword| ("KW", keyword.clone())),
+    );
+    tags.extend(citation.urls.iter().map(|url| ("UR", url.clone())));
+    if let Some(nnt) = &citation.nnt {
+        tags.push(("AN", nnt.to_string()));
+    }
+
+    let mut ris = String::new();
+    for (tag, value) in tags {
+        let value = one_line(&value);
+        if !value.is_empty() {
+            ris.push_str(&format!("{}  - {}\r\n", tag, value));
+        }
+    }
+    ris.push_str("ER  - \r\n");
+
+    Ok(ris)
+}
+
+/// Convert a TEF record to a CSL-JSON `thesis` item.
+///
+/// Names are split into `family` and `given`, a lowercase particle of the family name such as `de` is a
+/// `non-dropping-particle`. The grantors are the publisher and the kind of thesis is the genre.
+pub fn to_csl_json(mets: &Mets) -> Result<Value, Error> {
+    let (record, admin) = metadata(mets)?;
+    let citation = Citation::new(mets, record, admin);
+    let mut item = Map::new();
+
+    item.insert("id".into(), json!(citation.key()));
+    item.insert("type".into(), json!("thesis"));
+    item.insert("title".into(), json!(citation.title));
+    item.insert("author".into(), json!([csl_name(admin.auteur())]));
+    item.insert("genre".into(), json!(citation.r#type));
+    if !citation.grantors.is_empty() {
+        item.insert("publisher".into(), json!(citation.grantors.join("; ")));
+    }
+    if let Some(parts) = citation.date_parts {
+        item.insert("issued".into(), json!({ "date-parts": [parts] }));
+    }
+    if let Some(language) = citation.languages.first() {
+        item.insert("language".into(), json!(language));
+    }
+    if let Some(r#abstract) = citation.r#abstract {
+        item.insert("abstract".into(), json!(r#abstract));
+    }
+    if !citation.keywords.is_empty() {
+        item.insert("keyword".into(), json!(citation.keywords.join(", ")));
+    }
+    if let Some(url) = citation.urls.first() {
+        item.insert("URL".into(), json!(url));
+    }
+    if let Some(nnt) = &citation.nnt {
+        item.insert("number".into(), json!(nnt));
+    }
+
+    Ok(Value::Object(item))
+}
+
+/// Convert a TEF record to a CSL-JSON document, a list holding the `thesis` item
+pub fn to_csl_json_string(mets: &Mets) -> Result<String, Error> {
+    serde_json::to_string_pretty(&json!([to_csl_json(mets)?])).map_err(|err| Error::Export {
+        format: "CSL-JSON",
+        reason: err.to_string(),
+    })
+}
+
+/// Values shared by the citation formats
+struct Citation<'a> {
+    nnt: Option<Nnt>,
+    title: String,
+    r#type: &'static str,
+    grantors: Vec<String>,
+    /// `YYYY-MM-DD`, `YYYY-MM` or `YYYY`
+    date: Option<String>,
+    date_parts: Option<Vec<u16>>,
+    year: Option<u16>,
+    languages: Vec<String>,
+    /// Abstract in the language of the title, or else the first one
+    r#abstract: Option<&'a str>,
+    keywords: Vec<String>,
+    urls: Vec<String>,
+    auteur: &'a Auteur,
+}
+
+impl<'a> Citation<'a> {
+    fn new(mets: &Mets, record: &'a ThesisRecord, admin: &'a ThesisAdmin) -> Self {
+        let title = record.title();
+        let date = admin.date_accepted().date().ok();
+        let abstracts = record.r#abstract().as_deref().unwrap_or_default();
+
+        Self {
+            nnt: Nnt::from_mets(mets).ok(),
+            title: one_line(title.text()),
+            r#type: thesis_type(admin.thesis_degree().level().text()),
+            grantors: admin
+                .thesis_degree()
+                .grantor()
+                .iter()
+                .map(|grantor| grantor.nom().0.trim().to_string())
+                .filter(|nom| !nom.is_empty())
+                .collect(),
+            date: date.as_ref().map(|date| date.to_string()),
+            date_parts: date.as_ref().map(|date| {
+                std::iter::once(date.year())
+                    .chain(date.month().map(u16::from))
+                    .chain(date.day().map(u16::from))
+                    .collect()
+            }),
+            year: date.as_ref().map(|date| date.year()),
+            languages: record
+                .language()
+                .iter()
+                .map(|language| language.text().trim().to_string())
+                .filter(|language| !language.is_empty())
+                .collect(),
+            r#abstract: abstracts
+                .iter()
+                .find(|r#abstract| r#abstract.lang().trim() == title.lang().trim())
+                .or_else(|| abstracts.first())
+                .map(|r#abstract| r#abstract.text().trim()),
+            keywords: record
+                .subject()
+                .iter()
+                .flatten()
+                .map(|subject| subject.text().trim().to_string())
+                .filter(|subject| !subject.is_empty())
+                .collect(),
+            urls: edition_urls(mets),
+            auteur: admin.auteur(),
+        }
+    }
+
+    /// NNT, or else the family name of the author without accents nor particles and the year, e.g. `dupont2019`.
+    /// A name without Latin letters or digits is replaced by `these`.
+    fn key(&self) -> String {
+        if let Some(nnt) = &self.nnt {
+            return nnt.to_string();
+        }
+
+        let nom: String = matching_key(&self.auteur.nom().0, "")
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect();
+        let nom = match nom.is_empty() {
+            true => "these".to_string(),
+            false => nom,
+        };
+        let year = self.year.map(|year| year.to_string()).unwrap_or_default();
+
+        format!("{}{}", nom, year)
+    }
+}
+
+/// `Nom, Prénom`, a part holding ` and `, which separates the names of a BibTeX list, is braced
+fn bibtex_name(person: &dyn Person) -> String {
+    let protect = |part: &str| {
+        let part = escape_bibtex(part);
+        match part.to_lowercase().contains(" and ") {
+            true => format!("{{{}}}", part),
+            false => part,
+        }
+    };
+
+    sort_name(&protect(&person.nom().0), &protect(&person.prenom().0))
+}
+
+fn csl_name(person: &dyn Person) -> Value {
+    let nom = person.nom().0.trim();
+    let words: Vec<&str> = nom.split_whitespace().collect();
+    let particles = words
+        .iter()
+        .take_while(|word| {
+            word.chars().next().is_some_and(char::is_lowercase)
+                && PARTICLES.contains(&word.trim_end_matches('\'').to_lowercase().as_str())
+        })
+        .count();
+
+    let mut name = Map::new();
+    match particles {
+        particles if particles > 0 && particles < words.len() => {
+            name.insert("family".into(), json!(words[particles..].join(" ")));
+            name.insert(
+                "non-dropping-particle".into(),
+                json!(words[..particles].join(" ")),
+            );
+        }
+        _ => {
+            name.insert("family".into(), json!(nom));
+        }
+    }
+    let prenom = person.prenom().0.trim();
+    if !prenom.is_empty() {
+        name.insert("given".into(), json!(prenom));
+    }
+
+    Value::Object(name)
+}
+
+/// Escape the special characters of LaTeX
+fn escape_bibtex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in one_line(value).chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '%' | '&' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Value on a single line, runs of whitespace are collapsed
+fn one_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::{these_with, THESE};
+
+    fn without_nnt(nom: &str, date: &str) -> Mets {
+        let input = these_with(&[
+            (r#"xsi:type="tef:NNT""#, r#"xsi:type="dcterms:URI""#),
+            (
+                "<tef:nom>Dupont</tef:nom>",
+                &format!("<tef:nom>{nom}</tef:nom>"),
+            ),
+            (
+                r#"W3CDTF">2019-10-21</dcterms:dateAccepted>"#,
+                &format!(r#"W3CDTF">{date}</dcterms:dateAccepted>"#),
+            ),
+        ]);
+
+        parse_tef(&input).unwrap()
+    }
+
+    #[test]
+    fn key() {
+        let bibtex = |mets: &Mets| to_bibtex(mets).unwrap().lines().next().unwrap().to_string();
+
+        assert_eq!(
+            bibtex(&parse_tef(THESE).unwrap()),
+            "@phdthesis{2019LYSE1234,"
+        );
+        assert_eq!(
+            bibtex(&without_nnt("Élise", "2019-10-21")),
+            "@phdthesis{elise2019,"
+        );
+        assert_eq!(
+            bibtex(&without_nnt("de l'Œuvre", "2019")),
+            "@phdthesis{loeuvre2019,"
+        );
+        assert_eq!(bibtex(&without_nnt("王", "inconnue")), "@phdthesis{these,");
+    }
+
+    #[test]
+    fn bibtex_escapes() {
+        assert_eq!(
+            escape_bibtex("50% & $x_1$ #1 {a}\n~b^c\\d"),
+            r"50\% \& \$x\_1\$ \#1 \{a\} \textasciitilde{}b\textasciicircum{}c\textbackslash{}d"
+        );
+        assert_eq!(escape_bibtex("Étude des matériaux"), "Étude des matériaux");
+    }
+
+    #[test]
+    fn ris_lines() {
+        let input = THESE.replace(
+            "Résumé en français.",
+            "Résumé\n            sur   plusieurs\r\n lignes.",
+        );
+        let ris = to_ris(&parse_tef(&input).unwrap()).unwrap();
+
+        assert!(ris.contains("AB  - Résumé sur plusieurs lignes.\r\n"));
+        assert!(ris.ends_with("ER  - \r\n"));
+        assert!(ris
+            .split("\r\n")
+            .filter(|line| !line.is_empty())
+            .all(|line| line.len() >= 6 && &line[2..6] == "  - "));
+    }
+
+    #[test]
+    fn csl_particles() {
+        let mets = parse_tef(THESE).unwrap();
+        let admin = mets.thesis_admin().unwrap();
+
+        assert_eq!(
+            csl_name(&admin.directeur_these()[0]),
+            json!({ "family": "La Fontaine", "non-dropping-particle": "de", "given": "Marie" })
+        );
+        assert_eq!(
+            csl_name(&admin.rapporteur()[0]),
+            json!({ "family": "Van der Berg", "given": "Hans" })
+        );
+        assert_eq!(
+            csl_name(admin.auteur()),
+            json!({ "family": "Dupont", "given": "Jean-Pierre" })
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! schema.org `Thesis` in JSON-LD, to be embedded in the landing pages of the theses
+
+use serde_json::{json, Map, Value};
+
+use super::{distinct, metadata};
+use crate::error::Error;
+use crate::identifiers::Nnt;
+use crate::mets::Mets;
+use crate::tef::*;
+use crate::thesis::Thesis;
+
+/// Context of the documents
+pub const SCHEMA_ORG: &str = "https://schema.org";
+
+/// Convert a TEF record to a schema.org `Thesis`.
+///
+/// Texts keep their language as JSON-LD value objects, the author is a `Person` and the other persons are
+/// contributors qualified by a `Role`. Persons and organizations with an IdRef or Sudoc authority record are linked
+/// to it with `sameAs`, and each file of the electronic editions is a `MediaObject` of `encoding`.
+pub fn to_json_ld(mets: &Mets) -> Result<Value, Error> {
+    let (record, admin) = metadata(mets)?;
+    let mut thesis = Map::new();
+
+    thesis.insert("@context".into(), json!(SCHEMA_ORG));
+    thesis.insert("@type".into(), json!("Thesis"));
+    if let Ok(nnt) = Nnt::from_mets(mets) {
+        thesis.insert("identifier".into(), json!(nnt));
+    }
+
+    let title = record.title();
+    thesis.insert("name".into(), text(title.lang(), title.text()));
+    let alternatives: Vec<Value> = record
+        .alternative()
+        .iter()
+        .flatten()
+        .map(|alternative| text(alternative.lang(), alternative.text()))
+        .collect();
+    insert_all(&mut thesis, "alternateName", alternatives);
+
+    let languages: Vec<Value> = distinct(
+        record
+            .language()
+            .iter()
+            .map(|language| language.text().trim().to_string()),
+    )
+    .into_iter()
+    .map(Value::String)
+    .collect();
+    insert_all(&mut thesis, "inLanguage", languages);
+
+    let abstracts: Vec<Value> = record
+        .r#abstract()
+        .iter()
+        .flatten()
+        .map(|r#abstract| text(r#abstract.lang(), r#abstract.text()))
+        .collect();
+    insert_all(&mut thesis, "abstract", abstracts);
+
+    let mut contributors = vec![];
+    for (role, person) in admin.persons() {
+        match role {
+            Role::Author => {
+                thesis.insert("author".into(), self::person(person));
+            }
+            role => contributors.push(json!({
+                "@type": "Role",
+                "roleName": role.to_string(),
+                "contributor": self::person(person),
+            })),
+        }
+    }
+    insert_all(&mut thesis, "contributor", contributors);
+
+    let grantors: Vec<Value> = admin
+        .thesis_degree()
+        .grantor()
+        .iter()
+        .map(|grantor| organization(grantor))
+        .collect();
+    insert_all(&mut thesis, "sourceOrganization", grantors);
+
+    let degree = admin.thesis_degree();
+    let credential = match degree.name() {
+        Some(name) if !name.0.trim().is_empty() => name.0.trim(),
+        _ => degree.level().text().as_str(),
+    };
+    thesis.insert("inSupportOf".into(), json!(credential));
+
+    let date = admin.date_accepted().text().trim();
+    if !date.is_empty() {
+        thesis.insert("dateCreated".into(), json!(date));
+    }
+
+    let keywords: Vec<Value> = distinct(
+        record
+            .subject()
+            .iter()
+            .flatten()
+            .map(|subject| subject.text().trim().to_string()),
+    )
+    .into_iter()
+    .map(Value::String)
+    .collect();
+    if !keywords.is_empty() {
+        thesis.insert("keywords".into(), Value::Array(keywords));
+    }
+
+    let encodings = encodings(mets);
+    if !encodings.is_empty() {
+        thesis.insert("encoding".into(), Value::Array(encodings));
+    }
+
+    Ok(Value::Object(thesis))
+}
+
+/// Convert a TEF record to a schema.org `Thesis` serialized in JSON-LD
+pub fn to_json_ld_string(mets: &Mets) -> Result<String, Error> {
+    serde_json::to_string_pretty(&to_json_ld(mets)?).map_err(|err| Error::Export {
+        format: "JSON-LD",
+        reason: err.to_string(),
+    })
+}
+
+/// Text with its language
+fn text(lang: &str, text: &str) -> Value {
+    match lang.trim() {
+        "" => json!(text.trim()),
+        lang => json!({ "@language": lang, "@value": text.trim() }),
+    }
+}
+
+/// Insert a single value as is and several values as an array
+fn insert_all(object: &mut Map<String, Value>, key: &str, mut values: Vec<Value>) {
+    match values.len() {
+        0 => {}
+        1 => {
+            object.insert(key.into(), values.remove(0));
+        }
+        _ => {
+            object.insert(key.into(), Value::Array(values));
+        }
+    }
+}
+
+fn person(person: &dyn Person) -> Value {
+    let mut value = json!({
+        "@type": "Person",
+        "name": person.display_name(),
+        "familyName": person.nom().0.trim(),
+        "givenName": person.prenom().0.trim(),
+    });
+    if let Some(ppn) = person.ppn() {
+        value["sameAs"] = json!(ppn.idref_uri());
+    }
+
+    value
+}
+
+fn organization(organization: &dyn Organization) -> Value {
+    let mut value = json!({
+        "@type": "CollegeOrUniversity",
+        "name": organization.nom().0.trim(),
+    });
+    if let Some(ppn) = organization.ppn() {
+        value["sameAs"] = json!(ppn.idref_uri());
+    }
+
+    value
+}
+
+/// Files of the electronic editions located by a URL
+fn encodings(mets: &Mets) -> Vec<Value> {
+    let thesis = match Thesis::from_mets(mets) {
+        Ok(thesis) => thesis,
+        Err(_) => return vec![],
+    };
+
+    let mut urls = vec![];
+    let mut encodings = vec![];
+    for file in thesis
+        .editions()
+        .flat_map(|edition| edition.metadata().files())
+    {
+        let location = file.value();
+        let url = location.href().trim();
+        if location.loctype().trim() != "URL" || url.is_empty() || urls.contains(&url) {
+            continue;
+        }
+        urls.push(url);
+
+        let mut encoding = json!({ "@type": "MediaObject", "contentUrl": url });
+        if let Some(mimetype) = file.mimetype() {
+            encoding["encodingFormat"] = json!(mimetype.trim());
+        }
+        encodings.push(encoding);
+    }
+
+    encodings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::THESE;
+
+    #[test]
+    fn fixture() {
+        let thesis = to_json_ld(&parse_tef(THESE).unwrap()).unwrap();
+
+        assert_eq!(
+            thesis["author"],
+            json!({
+                "@type": "Person",
+                "familyName": "Dupont",
+                "givenName": "Jean-Pierre",
+                "name": "Jean-Pierre Dupont",
+                "sameAs": "https://www.idref.fr/123456789"
+            })
+        );
+
+        let contributors = thesis["contributor"].as_array().unwrap();
+        let roles: Vec<(&str, &str)> = contributors
+            .iter()
+            .map(|role| {
+                assert_eq!(role["@type"], "Role");
+                (
+                    role["roleName"].as_str().unwrap(),
+                    role["contributor"]["name"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            roles,
+            [
+                ("director", "Marie de La Fontaine"),
+                ("president", "Paul Martin"),
+                ("member", "Anne Durand"),
+                ("rapporteur", "Hans Van der Berg")
+            ]
+        );
+        assert_eq!(
+            contributors[0]["contributor"]["sameAs"],
+            "https://www.idref.fr/027189848"
+        );
+        assert!(contributors[1]["contributor"].get("sameAs").is_none());
+
+        assert_eq!(
+            thesis["sourceOrganization"]["sameAs"],
+            "https://www.idref.fr/137964781"
+        );
+        assert_eq!(
+            thesis["encoding"],
+            json!([{
+                "@type": "MediaObject",
+                "contentUrl": "https://theses.hal.science/tel-01234567/document",
+                "encodingFormat": "application/pdf"
+            }])
+        );
+    }
+}
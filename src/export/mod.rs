@@ -0,0 +1,95 @@
+//! Conversion of TEF records to other metadata formats
+
+use crate::edit::{THESIS_ADMIN, THESIS_RECORD};
+use crate::error::Error;
+use crate::mets::*;
+use crate::tef::*;
+use crate::thesis::Thesis;
+
+pub mod citation;
+pub mod jsonld;
+pub mod oai_dc;
+pub mod rdf;
+pub mod unimarc;
+
+pub use citation::{to_bibtex, to_csl_json, to_csl_json_string, to_ris};
+pub use jsonld::{to_json_ld, to_json_ld_string};
+pub use oai_dc::{to_oai_dc_string, write_oai_dc};
+pub use rdf::{to_ntriples, to_turtle};
+pub use unimarc::{to_marcxml_string, to_unimarc, write_marcxml};
+
+/// Descriptive and administrative metadata of the thesis, see [`Mets::thesis_record`] and [`Mets::thesis_admin`]
+pub(crate) fn metadata(mets: &Mets) -> Result<(&ThesisRecord, &ThesisAdmin), Error> {
+    let record = mets
+        .thesis_record()
+        .ok_or_else(|| Error::missing(THESIS_RECORD))?;
+    let admin = mets
+        .thesis_admin()
+        .ok_or_else(|| Error::missing(THESIS_ADMIN))?;
+
+    Ok((record, admin))
+}
+
+/// Kind of thesis, as written in a dissertation note, e.g. `Thèse de doctorat`
+pub(crate) fn thesis_type(level: &ThesisDegreeLevelValues) -> &'static str {
+    match level {
+        ThesisDegreeLevelValues::Doctorat => "Thèse de doctorat",
+        ThesisDegreeLevelValues::DoctoratEtat => "Thèse d'État",
+        ThesisDegreeLevelValues::Doctorat3eCycle => "Thèse de troisième cycle",
+    }
+}
+
+/// Identifiers of the electronic editions and locations of their files, without duplicates
+///
+/// Records without a thesis div in their `mets:structMap` have no edition.
+pub(crate) fn edition_urls(mets: &Mets) -> Vec<String> {
+    let thesis = match Thesis::from_mets(mets) {
+        Ok(thesis) => thesis,
+        Err(_) => return vec![],
+    };
+
+    let mut urls = vec![];
+    for edition in thesis.editions() {
+        let identifiers = edition
+            .edition()
+            .map(|edition| edition.values().as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|value| match value {
+                EditionValues::Identifier(identifier) => Some(identifier.text().trim()),
+                _ => None,
+            });
+        let files = edition
+            .metadata()
+            .files()
+            .iter()
+            .map(|file| file.value())
+            .filter(|location| location.loctype().trim() == "URL")
+            .map(|location| location.href().trim());
+
+        urls.extend(identifiers.chain(files).map(str::to_string));
+    }
+
+    distinct(urls)
+}
+
+/// Non-empty values in their first order of appearance
+pub(crate) fn distinct<I: IntoIterator<Item = String>>(values: I) -> Vec<String> {
+    let mut distinct: Vec<String> = vec![];
+
+    for value in values {
+        if !value.is_empty() && !distinct.contains(&value) {
+            distinct.push(value);
+        }
+    }
+
+    distinct
+}
+
+/// Read a buffer written by an exporter
+pub(crate) fn into_string(buffer: Vec<u8>) -> Result<String, Error> {
+    String::from_utf8(buffer).map_err(|e| Error::InvalidEncoding {
+        encoding: "UTF-8",
+        offset: e.utf8_error().valid_up_to(),
+    })
+}
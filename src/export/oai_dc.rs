@@ -0,0 +1,247 @@
+//! Simple Dublin Core, the `oai_dc` metadata format every OAI-PMH repository must expose
+
+use std::io::Write;
+
+use super::{distinct, edition_urls, into_string, metadata};
+use crate::error::Error;
+use crate::identifiers::Nnt;
+use crate::mets::Mets;
+use crate::mets_rights::RightsDeclarationMDValues;
+use crate::ser::XmlWriter;
+use crate::tef::Role;
+use crate::thesis::Thesis;
+
+/// Namespaces declared on the `oai_dc:dc` element
+pub const OAI_DC_NAMESPACES: [(&str, &str); 3] = [
+    (
+        "xmlns:oai_dc",
+        "http://www.openarchives.org/OAI/2.0/oai_dc/",
+    ),
+    ("xmlns:dc", "http://purl.org/dc/elements/1.1/"),
+    ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+];
+
+const SCHEMA_LOCATION: &str =
+    "http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd";
+
+/// Convert a TEF record to an `oai_dc:dc` document
+pub fn to_oai_dc_string(mets: &Mets) -> Result<String, Error> {
+    let mut buffer = Vec::new();
+    write_oai_dc(mets, &mut buffer)?;
+
+    into_string(buffer)
+}
+
+/// Write a TEF record as an `oai_dc:dc` document.
+///
+/// Titles, subjects and abstracts keep their language, the author is the creator and the other persons are
+/// contributors, the grantors are the publishers, and the NNT and the URLs of the editions are identifiers. Empty
+/// values are skipped, and so is a blank language.
+pub fn write_oai_dc<W: Write>(mets: &Mets, writer: W) -> Result<(), Error> {
+    let (record, admin) = metadata(mets)?;
+
+    let mut attributes: Vec<(&str, Option<&str>)> = OAI_DC_NAMESPACES
+        .iter()
+        .map(|(key, value)| (*key, Some(*value)))
+        .collect();
+    attributes.push(("xsi:schemaLocation", Some(SCHEMA_LOCATION)));
+
+    let mut w = XmlWriter::new(writer);
+    w.declaration()?;
+    w.element("oai_dc:dc", &attributes, |w| {
+        let title = record.title();
+        dc(w, "dc:title", Some(title.lang()), title.text())?;
+        for alternative in record.alternative().iter().flatten() {
+            dc(w, "dc:title", Some(alternative.lang()), alternative.text())?;
+        }
+
+        for (role, person) in admin.persons() {
+            let name = match role {
+                Role::Author => "dc:creator",
+                _ => "dc:contributor",
+            };
+            dc(w, name, None, &person.sort_name())?;
+        }
+
+        let discipline = admin.thesis_degree().discipline();
+        dc(w, "dc:subject", Some(discipline.lang()), discipline.text())?;
+        for subject in record.subject().iter().flatten() {
+            dc(w, "dc:subject", subject.lang().as_deref(), subject.text())?;
+        }
+        let headings = record
+            .sujet_rameau()
+            .iter()
+            .flat_map(|sujet_rameau| sujet_rameau.values())
+            .flatten()
+            .map(|vedette| vedette.heading());
+        for heading in distinct(headings) {
+            dc(w, "dc:subject", Some("fr"), &heading)?;
+        }
+
+        for r#abstract in record.r#abstract().iter().flatten() {
+            dc(
+                w,
+                "dc:description",
+                Some(r#abstract.lang()),
+                r#abstract.text(),
+            )?;
+        }
+
+        for grantor in admin.thesis_degree().grantor() {
+            dc(w, "dc:publisher", None, &grantor.nom().0)?;
+        }
+
+        dc(w, "dc:date", None, admin.date_accepted().text())?;
+
+        for r#type in distinct(record.r#type().iter().map(|t| t.text().trim().to_string())) {
+            dc(w, "dc:type", None, &r#type)?;
+        }
+
+        for format in distinct(formats(mets)) {
+            dc(w, "dc:format", None, &format)?;
+        }
+
+        let identifiers = Nnt::from_mets(mets)
+            .map(|nnt| nnt.to_string())
+            .into_iter()
+            .chain(edition_urls(mets));
+        for identifier in distinct(identifiers) {
+            dc(w, "dc:identifier", None, &identifier)?;
+        }
+
+        for language in distinct(
+            record
+                .language()
+                .iter()
+                .map(|language| language.text().trim().to_string()),
+        ) {
+            dc(w, "dc:language", None, &language)?;
+        }
+
+        for coverage in record.coverage().iter().flatten() {
+            dc(w, "dc:coverage", None, &coverage.0)?;
+        }
+
+        for rights in distinct(rights(mets)) {
+            dc(w, "dc:rights", None, &rights)?;
+        }
+
+        Ok(())
+    })?;
+    w.into_inner().flush()?;
+
+    Ok(())
+}
+
+/// Dublin Core element, unless its value is empty, with its language unless it is blank
+fn dc<W: Write>(
+    w: &mut XmlWriter<W>,
+    name: &str,
+    lang: Option<&str>,
+    text: &str,
+) -> Result<(), Error> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(());
+    }
+    let lang = lang.map(str::trim).filter(|lang| !lang.is_empty());
+
+    w.text(name, &[("xml:lang", lang)], text)
+}
+
+/// Types of the files of the thesis
+fn formats(mets: &Mets) -> Vec<String> {
+    let thesis = match Thesis::from_mets(mets) {
+        Ok(thesis) => thesis,
+        Err(_) => return vec![],
+    };
+
+    thesis
+        .editions()
+        .flat_map(|edition| edition.metadata().files())
+        .filter_map(|file| file.mimetype().as_deref())
+        .map(|mimetype| mimetype.trim().to_string())
+        .collect()
+}
+
+/// Rights declarations of the thesis, its versions and their editions
+fn rights(mets: &Mets) -> Vec<String> {
+    let thesis = match Thesis::from_mets(mets) {
+        Ok(thesis) => thesis,
+        Err(_) => return vec![],
+    };
+
+    let metadata = std::iter::once(thesis.entity().metadata())
+        .chain(thesis.versions().iter().map(|version| version.metadata()))
+        .chain(thesis.editions().map(|edition| edition.metadata()));
+
+    metadata
+        .flat_map(|metadata| metadata.rights_declarations())
+        .flat_map(|declaration| declaration.values())
+        .filter_map(|value| match value {
+            RightsDeclarationMDValues::RightsDeclaration(declaration) => {
+                Some(declaration.0.trim().to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::THESE;
+
+    #[test]
+    fn fixture() {
+        let output = to_oai_dc_string(&parse_tef(THESE).unwrap()).unwrap();
+
+        assert_eq!(
+            output,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<oai_dc:dc xmlns:oai_dc="http://www.openarchives.org/OAI/2.0/oai_dc/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd">
+  <dc:title xml:lang="fr">Étude des propriétés des matériaux</dc:title>
+  <dc:title xml:lang="en">Study of material properties</dc:title>
+  <dc:creator>Dupont, Jean-Pierre</dc:creator>
+  <dc:contributor>de La Fontaine, Marie</dc:contributor>
+  <dc:contributor>Martin, Paul</dc:contributor>
+  <dc:contributor>Durand, Anne</dc:contributor>
+  <dc:contributor>Van der Berg, Hans</dc:contributor>
+  <dc:subject xml:lang="fr">Physique</dc:subject>
+  <dc:subject xml:lang="fr">Matériaux</dc:subject>
+  <dc:subject xml:lang="en">Materials</dc:subject>
+  <dc:subject xml:lang="fr">Matériaux -- Thèses et écrits académiques</dc:subject>
+  <dc:description xml:lang="fr">Résumé en français.</dc:description>
+  <dc:description xml:lang="en">English abstract.</dc:description>
+  <dc:publisher>Université de Lyon</dc:publisher>
+  <dc:date>2019-10-21</dc:date>
+  <dc:type>Electronic Thesis or Dissertation</dc:type>
+  <dc:type>Text</dc:type>
+  <dc:format>application/pdf</dc:format>
+  <dc:identifier>2019LYSE1234</dc:identifier>
+  <dc:identifier>https://theses.hal.science/tel-01234567</dc:identifier>
+  <dc:identifier>https://theses.hal.science/tel-01234567/document</dc:identifier>
+  <dc:language>fr</dc:language>
+  <dc:rights>Droits réservés</dc:rights>
+</oai_dc:dc>"#
+        );
+    }
+
+    #[test]
+    fn empty_values() {
+        let mut mets = parse_tef(THESE).unwrap();
+        let record = mets.thesis_record_mut().unwrap();
+        record.title_mut().set_lang(" ".to_string());
+        for subject in record.subject_mut().iter_mut().flatten() {
+            subject.set_text(" ".to_string());
+        }
+        let output = to_oai_dc_string(&mets).unwrap();
+
+        assert!(output.contains("<dc:title>Étude des propriétés des matériaux</dc:title>"));
+        assert!(!output.contains(r#"xml:lang=" ""#));
+        assert_eq!(output.matches("<dc:subject").count(), 2);
+        assert!(!output.contains("<dc:subject/>"));
+    }
+}
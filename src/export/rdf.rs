@@ -0,0 +1,623 @@
+//! RDF description of a thesis with the Dublin Core, BIBO and FOAF vocabularies, written as N-Triples or Turtle
+
+use std::fmt::Write;
+
+use getset::Getters;
+
+use super::metadata;
+use crate::date::{DatePrecision, TefDate};
+use crate::error::Error;
+use crate::identifiers::{Nnt, Ppn, PPN_SOURCES};
+use crate::mets::{Div, Mets};
+use crate::mets_rights::RightsDeclarationMDValues;
+use crate::tef::*;
+use crate::thesis::{Metadata, Thesis};
+
+/// Base of the IRIs minted from the NNT
+pub const DEFAULT_BASE: &str = "http://www.theses.fr/";
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+const DCTERMS: &str = "http://purl.org/dc/terms/";
+const BIBO: &str = "http://purl.org/ontology/bibo/";
+const FOAF: &str = "http://xmlns.com/foaf/0.1/";
+
+/// Prefixes of the Turtle documents
+pub const PREFIXES: [(&str, &str); 5] = [
+    ("rdf", RDF),
+    ("xsd", XSD),
+    ("dcterms", DCTERMS),
+    ("bibo", BIBO),
+    ("foaf", FOAF),
+];
+
+/// Node or value of a triple
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Iri(String),
+    /// Blank node, by its label
+    Blank(String),
+    Literal {
+        value: String,
+        language: Option<String>,
+        datatype: Option<String>,
+    },
+}
+
+impl Term {
+    fn iri<T: Into<String>>(iri: T) -> Self {
+        Term::Iri(iri.into())
+    }
+
+    fn literal<T: AsRef<str>>(value: T) -> Self {
+        Term::Literal {
+            value: value.as_ref().trim().to_string(),
+            language: None,
+            datatype: None,
+        }
+    }
+
+    /// Literal in a language, a plain literal when the language is empty
+    fn text<T: AsRef<str>>(lang: &str, value: T) -> Self {
+        Term::Literal {
+            value: value.as_ref().trim().to_string(),
+            language: Some(lang.trim().to_string()).filter(|lang| !lang.is_empty()),
+            datatype: None,
+        }
+    }
+
+    fn typed<T: AsRef<str>>(value: T, datatype: &str) -> Self {
+        Term::Literal {
+            value: value.as_ref().trim().to_string(),
+            language: None,
+            datatype: Some(format!("{}{}", XSD, datatype)),
+        }
+    }
+
+    /// Date typed `xsd:gYear`, `xsd:gYearMonth` or `xsd:date` according to its precision
+    fn date(date: &TefDate) -> Self {
+        let datatype = match date.precision() {
+            DatePrecision::Year => "gYear",
+            DatePrecision::Month => "gYearMonth",
+            DatePrecision::Day => "date",
+        };
+        Term::typed(date.to_string(), datatype)
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Term::Iri(iri) => iri.is_empty(),
+            Term::Blank(_) => false,
+            Term::Literal { value, .. } => value.is_empty(),
+        }
+    }
+
+    fn to_ntriples(&self) -> String {
+        match self {
+            Term::Iri(iri) => format!("<{}>", escape_iri(iri)),
+            Term::Blank(label) => format!("_:{}", label),
+            Term::Literal {
+                value,
+                language,
+                datatype,
+            } => {
+                let mut literal = format!("\"{}\"", escape_literal(value));
+                if let Some(language) = language {
+                    literal.push_str(&format!("@{}", language));
+                } else if let Some(datatype) = datatype {
+                    literal.push_str(&format!("^^<{}>", escape_iri(datatype)));
+                }
+                literal
+            }
+        }
+    }
+
+    fn to_turtle(&self) -> String {
+        match self {
+            Term::Iri(iri) => compact(iri).unwrap_or_else(|| self.to_ntriples()),
+            Term::Literal {
+                value,
+                language: None,
+                datatype: Some(datatype),
+            } => match compact(datatype) {
+                Some(datatype) => format!("\"{}\"^^{}", escape_literal(value), datatype),
+                None => self.to_ntriples(),
+            },
+            term => term.to_ntriples(),
+        }
+    }
+}
+
+/// Statement of a graph, the predicate is an IRI
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct Triple {
+    subject: Term,
+    predicate: String,
+    object: Term,
+}
+
+/// RDF graph of a thesis
+///
+/// The thesis is a `bibo:Thesis` identified by the NNT under the base IRI, e.g.
+/// `http://www.theses.fr/2019LYSE1234`. Its versions and their editions get IRIs below it, named after the `ID`
+/// of their `mets:div`, and are linked with `dcterms:hasVersion` and `dcterms:hasFormat`. Persons and organizations
+/// with an IdRef or Sudoc authority record are identified by their IdRef IRI, the others are blank nodes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct Graph {
+    triples: Vec<Triple>,
+}
+
+impl Graph {
+    /// Graph of a thesis with IRIs under [`DEFAULT_BASE`]
+    pub fn from_mets(mets: &Mets) -> Result<Self, Error> {
+        Self::from_mets_with_base(mets, DEFAULT_BASE)
+    }
+
+    /// Graph of a thesis with IRIs under the given base, e.g. `https://data.example.org/these/`
+    pub fn from_mets_with_base(mets: &Mets, base: &str) -> Result<Self, Error> {
+        let (record, admin) = metadata(mets)?;
+        let nnt = Nnt::from_mets(mets)?;
+
+        let work = format!("{}{}", base, nnt);
+        let mut graph = Builder::default();
+        let thesis = Term::iri(&work);
+
+        graph.add(&thesis, RDF, "type", Term::iri(format!("{}Thesis", BIBO)));
+        if matches!(
+            admin.thesis_degree().level().text(),
+            ThesisDegreeLevelValues::Doctorat
+        ) {
+            graph.add(
+                &thesis,
+                BIBO,
+                "degree",
+                Term::iri(format!("{}degrees/phd", BIBO)),
+            );
+        }
+        graph.add(&thesis, DCTERMS, "identifier", Term::literal(nnt.as_str()));
+
+        let title = record.title();
+        graph.add(
+            &thesis,
+            DCTERMS,
+            "title",
+            Term::text(title.lang(), title.text()),
+        );
+        for alternative in record.alternative().iter().flatten() {
+            graph.add(
+                &thesis,
+                DCTERMS,
+                "alternative",
+                Term::text(alternative.lang(), alternative.text()),
+            );
+        }
+        for r#abstract in record.r#abstract().iter().flatten() {
+            graph.add(
+                &thesis,
+                DCTERMS,
+                "abstract",
+                Term::text(r#abstract.lang(), r#abstract.text()),
+            );
+        }
+        for language in record.language() {
+            graph.add(&thesis, DCTERMS, "language", Term::literal(language.text()));
+        }
+
+        for subject in record.subject().iter().flatten() {
+            let lang = subject.lang().as_deref().unwrap_or_default();
+            graph.add(
+                &thesis,
+                DCTERMS,
+                "subject",
+                Term::text(lang, subject.text()),
+            );
+        }
+        let vedettes = record
+            .sujet_rameau()
+            .iter()
+            .flat_map(|sujet_rameau| sujet_rameau.values())
+            .flatten();
+        for vedette in vedettes {
+            graph.add(&thesis, DCTERMS, "subject", heading(vedette));
+        }
+
+        if let Ok(date) = admin.date_accepted().date() {
+            graph.add(&thesis, DCTERMS, "dateAccepted", Term::date(&date));
+        }
+
+        for (role, person) in admin.persons() {
+            let node = graph.person(person);
+            let property = match role {
+                Role::Author => "creator",
+                _ => "contributor",
+            };
+            graph.add(&thesis, DCTERMS, property, node);
+        }
+
+        for grantor in admin.thesis_degree().grantor() {
+            let node = graph.organization(grantor);
+            graph.add(&thesis, DCTERMS, "publisher", node);
+        }
+
+        if let Ok(entity) = Thesis::from_mets(mets) {
+            graph.rights(&thesis, entity.entity().metadata());
+
+            for (index, version) in entity.versions().iter().enumerate() {
+                let version_iri =
+                    Term::iri(child(&work, version.metadata().div(), "version", index));
+                graph.add(&thesis, DCTERMS, "hasVersion", version_iri.clone());
+                graph.add(&version_iri, DCTERMS, "isVersionOf", thesis.clone());
+                graph.rights(&version_iri, version.metadata());
+
+                for (index, edition) in version.editions().iter().enumerate() {
+                    let edition_iri =
+                        Term::iri(child(&work, edition.metadata().div(), "edition", index));
+                    graph.add(
+                        &edition_iri,
+                        RDF,
+                        "type",
+                        Term::iri(format!("{}Document", BIBO)),
+                    );
+                    graph.add(&version_iri, DCTERMS, "hasFormat", edition_iri.clone());
+                    graph.add(&edition_iri, DCTERMS, "isFormatOf", version_iri.clone());
+                    graph.edition(&edition_iri, edition.edition(), edition.metadata());
+                }
+            }
+        }
+
+        Ok(Self {
+            triples: graph.triples,
+        })
+    }
+
+    /// Graph in N-Triples, one triple per line
+    pub fn to_ntriples(&self) -> String {
+        let mut ntriples = String::new();
+
+        for triple in &self.triples {
+            let _ = writeln!(
+                ntriples,
+                "{} <{}> {} .",
+                triple.subject.to_ntriples(),
+                escape_iri(&triple.predicate),
+                triple.object.to_ntriples()
+            );
+        }
+
+        ntriples
+    }
+
+    /// Graph in Turtle, the triples are grouped by subject in their order of appearance
+    pub fn to_turtle(&self) -> String {
+        let mut turtle = String::new();
+
+        for (prefix, namespace) in PREFIXES {
+            let _ = writeln!(turtle, "@prefix {}: <{}> .", prefix, namespace);
+        }
+
+        let mut subjects: Vec<&Term> = vec![];
+        for triple in &self.triples {
+            if !subjects.contains(&&triple.subject) {
+                subjects.push(&triple.subject);
+            }
+        }
+
+        for subject in subjects {
+            let _ = write!(turtle, "\n{}", subject.to_turtle());
+
+            let mut predicate: Option<&str> = None;
+            for triple in self.triples.iter().filter(|t| &t.subject == subject) {
+                if predicate == Some(triple.predicate.as_str()) {
+                    let _ = write!(turtle, " ,\n        {}", triple.object.to_turtle());
+                    continue;
+                }
+
+                let separator = match predicate {
+                    Some(_) => " ;\n   ",
+                    None => "\n   ",
+                };
+                let name = match triple.predicate.strip_prefix(RDF) {
+                    Some("type") => "a".to_string(),
+                    _ => compact(&triple.predicate)
+                        .unwrap_or_else(|| format!("<{}>", escape_iri(&triple.predicate))),
+                };
+                let _ = write!(
+                    turtle,
+                    "{} {} {}",
+                    separator,
+                    name,
+                    triple.object.to_turtle()
+                );
+                predicate = Some(&triple.predicate);
+            }
+
+            turtle.push_str(" .\n");
+        }
+
+        turtle
+    }
+}
+
+/// Convert a TEF record to RDF in N-Triples, with IRIs under [`DEFAULT_BASE`]
+pub fn to_ntriples(mets: &Mets) -> Result<String, Error> {
+    Ok(Graph::from_mets(mets)?.to_ntriples())
+}
+
+/// Convert a TEF record to RDF in Turtle, with IRIs under [`DEFAULT_BASE`]
+pub fn to_turtle(mets: &Mets) -> Result<String, Error> {
+    Ok(Graph::from_mets(mets)?.to_turtle())
+}
+
+/// Triples being collected, with the counter of the blank nodes
+#[derive(Default)]
+struct Builder {
+    triples: Vec<Triple>,
+    blanks: usize,
+}
+
+impl Builder {
+    /// Add a triple, unless its object is empty or the triple is already there
+    fn add(&mut self, subject: &Term, namespace: &str, name: &str, object: Term) {
+        if object.is_empty() {
+            return;
+        }
+
+        let triple = Triple {
+            subject: subject.clone(),
+            predicate: format!("{}{}", namespace, name),
+            object,
+        };
+        if !self.triples.contains(&triple) {
+            self.triples.push(triple);
+        }
+    }
+
+    /// IdRef IRI of an authority record, or a new blank node
+    fn node(&mut self, ppn: Option<Ppn>, label: &str) -> Term {
+        match ppn {
+            Some(ppn) => Term::iri(ppn.idref_iri()),
+            None => {
+                self.blanks += 1;
+                Term::Blank(format!("{}{}", label, self.blanks))
+            }
+        }
+    }
+
+    fn person(&mut self, person: &dyn Person) -> Term {
+        let node = self.node(person.ppn(), "person");
+
+        self.add(&node, RDF, "type", Term::iri(format!("{}Person", FOAF)));
+        self.add(&node, FOAF, "name", Term::literal(person.display_name()));
+        self.add(&node, FOAF, "familyName", Term::literal(&person.nom().0));
+        self.add(&node, FOAF, "givenName", Term::literal(&person.prenom().0));
+
+        node
+    }
+
+    fn organization(&mut self, organization: &dyn Organization) -> Term {
+        let node = self.node(organization.ppn(), "organization");
+
+        self.add(
+            &node,
+            RDF,
+            "type",
+            Term::iri(format!("{}Organization", FOAF)),
+        );
+        self.add(&node, FOAF, "name", Term::literal(&organization.nom().0));
+
+        node
+    }
+
+    fn rights(&mut self, subject: &Term, metadata: &Metadata) {
+        let declarations = metadata
+            .rights_declarations()
+            .flat_map(|declaration| declaration.values());
+
+        for value in declarations {
+            if let RightsDeclarationMDValues::RightsDeclaration(declaration) = value {
+                self.add(subject, DCTERMS, "rights", Term::literal(&declaration.0));
+            }
+        }
+    }
+
+    /// Identifiers, medium and publisher of an edition, and its files located by a URL
+    fn edition(&mut self, subject: &Term, edition: Option<&Edition>, metadata: &Metadata) {
+        for value in edition
+            .map(|edition| edition.values().as_slice())
+            .unwrap_or_default()
+        {
+            match value {
+                EditionValues::Identifier(identifier) => self.add(
+                    subject,
+                    DCTERMS,
+                    "identifier",
+                    Term::literal(identifier.text()),
+                ),
+                EditionValues::Medium(medium) => {
+                    self.add(subject, DCTERMS, "format", Term::literal(medium.text()))
+                }
+                EditionValues::Issued(issued) => {
+                    if let Ok(date) = issued.date() {
+                        self.add(subject, DCTERMS, "issued", Term::date(&date))
+                    }
+                }
+                EditionValues::Editeur(editeur) => {
+                    let node = self.organization(editeur);
+                    self.add(subject, DCTERMS, "publisher", node);
+                }
+                _ => {}
+            }
+        }
+
+        for file in metadata.files() {
+            let location = file.value();
+            if location.loctype().trim() != "URL" {
+                continue;
+            }
+
+            let file_iri = Term::iri(location.href().trim());
+            self.add(subject, DCTERMS, "hasPart", file_iri.clone());
+            if let Some(mimetype) = file.mimetype() {
+                self.add(&file_iri, DCTERMS, "format", Term::literal(mimetype));
+            }
+        }
+    }
+}
+
+/// IdRef IRI of a Rameau heading, or its text when it has no authority record
+fn heading(vedette: &SujetRameauValues) -> Term {
+    let entree = vedette.element_entree();
+    let source = entree
+        .autorite_source()
+        .as_deref()
+        .unwrap_or("sudoc")
+        .trim()
+        .to_lowercase();
+
+    let ppn = match PPN_SOURCES.contains(&source.as_str()) && vedette.subdivisions().is_empty() {
+        true => entree
+            .autorite_externe()
+            .as_deref()
+            .and_then(|id| id.parse::<Ppn>().ok()),
+        false => None,
+    };
+
+    match ppn {
+        Some(ppn) => Term::iri(ppn.idref_iri()),
+        None => Term::text("fr", vedette.heading()),
+    }
+}
+
+/// IRI of a version or an edition, named after the `ID` of its div or else its kind and position
+fn child(work: &str, div: &Div, kind: &str, index: usize) -> String {
+    match div.id().as_deref().map(str::trim) {
+        Some(id) if !id.is_empty() => format!("{}/{}", work, percent_encode(id)),
+        _ => format!("{}/{}/{}", work, kind, index + 1),
+    }
+}
+
+/// Encode everything but the unreserved characters of RFC 3986
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Prefixed name of an IRI, when its local part is a simple name
+fn compact(iri: &str) -> Option<String> {
+    PREFIXES.iter().find_map(|(prefix, namespace)| {
+        let local = iri.strip_prefix(namespace)?;
+        let simple = local.chars().next().is_some_and(char::is_alphabetic)
+            && local.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        simple.then(|| format!("{}:{}", prefix, local))
+    })
+}
+
+fn escape_literal(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Percent-encode the characters IRIs may not hold in N-Triples and Turtle, which don't allow escape sequences
+/// for them
+fn escape_iri(iri: &str) -> String {
+    iri.chars()
+        .map(|c| match c {
+            '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' | ' ' => {
+                percent_encode(c.encode_utf8(&mut [0; 4]))
+            }
+            c if c.is_control() => percent_encode(c.encode_utf8(&mut [0; 4])),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::THESE;
+
+    #[test]
+    fn ntriples() {
+        let output = to_ntriples(&parse_tef(THESE).unwrap()).unwrap();
+        let triples: Vec<&str> = output.lines().collect();
+
+        for triple in [
+            "<http://www.theses.fr/2019LYSE1234> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://purl.org/ontology/bibo/Thesis> .",
+            "<http://www.theses.fr/2019LYSE1234> <http://purl.org/dc/terms/dateAccepted> \"2019-10-21\"^^<http://www.w3.org/2001/XMLSchema#date> .",
+            "<http://www.theses.fr/2019LYSE1234> <http://purl.org/dc/terms/creator> <http://www.idref.fr/123456789/id> .",
+            "<http://www.theses.fr/2019LYSE1234> <http://purl.org/dc/terms/contributor> <http://www.idref.fr/027189848/id> .",
+            "<http://www.theses.fr/2019LYSE1234> <http://purl.org/dc/terms/contributor> _:person1 .",
+            "<http://www.theses.fr/2019LYSE1234> <http://purl.org/dc/terms/publisher> <http://www.idref.fr/137964781/id> .",
+            "<http://www.theses.fr/2019LYSE1234> <http://purl.org/dc/terms/hasVersion> <http://www.theses.fr/2019LYSE1234/version_complete> .",
+            "<http://www.theses.fr/2019LYSE1234/version_complete> <http://purl.org/dc/terms/isVersionOf> <http://www.theses.fr/2019LYSE1234> .",
+            "<http://www.theses.fr/2019LYSE1234/version_complete> <http://purl.org/dc/terms/hasFormat> <http://www.theses.fr/2019LYSE1234/edition_1> .",
+            "<http://www.theses.fr/2019LYSE1234/edition_1> <http://purl.org/dc/terms/isFormatOf> <http://www.theses.fr/2019LYSE1234/version_complete> .",
+            "<http://www.theses.fr/2019LYSE1234/edition_1> <http://purl.org/dc/terms/issued> \"2019-12-01\"^^<http://www.w3.org/2001/XMLSchema#date> .",
+            "<http://www.theses.fr/2019LYSE1234/edition_1> <http://purl.org/dc/terms/hasPart> <https://theses.hal.science/tel-01234567/document> .",
+        ] {
+            assert!(triples.contains(&triple), "{}", triple);
+        }
+        assert!(triples.iter().all(|triple| triple.ends_with(" .")));
+    }
+
+    #[test]
+    fn turtle() {
+        let output = to_turtle(&parse_tef(THESE).unwrap()).unwrap();
+
+        assert!(output.contains(
+            r#"<http://www.theses.fr/2019LYSE1234/version_complete>
+    dcterms:isVersionOf <http://www.theses.fr/2019LYSE1234> ;
+    dcterms:hasFormat <http://www.theses.fr/2019LYSE1234/edition_1> .
+
+<http://www.theses.fr/2019LYSE1234/edition_1>
+    a bibo:Document ;
+    dcterms:isFormatOf <http://www.theses.fr/2019LYSE1234/version_complete> ;
+    dcterms:format "application/pdf" ;
+    dcterms:identifier "https://theses.hal.science/tel-01234567" ;
+    dcterms:issued "2019-12-01"^^xsd:date ;
+    dcterms:hasPart <https://theses.hal.science/tel-01234567/document> .
+"#
+        ));
+        assert!(output.contains(
+            r#"<http://www.idref.fr/027189848/id>
+    a foaf:Person ;
+    foaf:name "Marie de La Fontaine" ;
+    foaf:familyName "de La Fontaine" ;
+    foaf:givenName "Marie" .
+"#
+        ));
+    }
+
+    #[test]
+    fn iri_escapes() {
+        assert_eq!(
+            escape_iri("https://example.org/a b<c>{d}|\"e\"\u{7f}é"),
+            "https://example.org/a%20b%3Cc%3E%7Bd%7D%7C%22e%22%7Fé"
+        );
+    }
+}
@@ -0,0 +1,576 @@
+//! UNIMARC bibliographic records, as catalogued in the Sudoc, serialized as ISO 2709 or MARCXML
+
+use std::io::Write;
+
+use getset::Getters;
+
+use super::{distinct, edition_urls, into_string, metadata, thesis_type};
+use crate::error::Error;
+use crate::identifiers::{Nnt, Ppn, PPN_SOURCES};
+use crate::mets::{Mets, MetsValues};
+use crate::ser::XmlWriter;
+use crate::tef::*;
+
+/// Namespace of the MARCXML documents
+pub const MARCXML_NAMESPACE: &str = "http://www.loc.gov/MARC21/slim";
+
+const SUBFIELD_DELIMITER: u8 = 0x1f;
+const FIELD_TERMINATOR: u8 = 0x1e;
+const RECORD_TERMINATOR: u8 = 0x1d;
+
+/// Two letter language codes of ISO 639-1 and their bibliographic three letter code of ISO 639-2
+const LANGUAGES: [(&str, &str); 32] = [
+    ("ar", "ara"),
+    ("br", "bre"),
+    ("ca", "cat"),
+    ("co", "cos"),
+    ("cs", "cze"),
+    ("da", "dan"),
+    ("de", "ger"),
+    ("el", "gre"),
+    ("en", "eng"),
+    ("es", "spa"),
+    ("eu", "baq"),
+    ("fa", "per"),
+    ("fi", "fin"),
+    ("fr", "fre"),
+    ("he", "heb"),
+    ("hu", "hun"),
+    ("it", "ita"),
+    ("ja", "jpn"),
+    ("ko", "kor"),
+    ("la", "lat"),
+    ("nl", "dut"),
+    ("no", "nor"),
+    ("oc", "oci"),
+    ("pl", "pol"),
+    ("pt", "por"),
+    ("ro", "rum"),
+    ("ru", "rus"),
+    ("sv", "swe"),
+    ("tr", "tur"),
+    ("uk", "ukr"),
+    ("vi", "vie"),
+    ("zh", "chi"),
+];
+
+/// Zone of a UNIMARC record
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct Field {
+    tag: &'static str,
+    indicators: [char; 2],
+    subfields: Vec<Subfield>,
+}
+
+/// Sous-zone of a UNIMARC field
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct Subfield {
+    code: char,
+    value: String,
+}
+
+impl Field {
+    fn new(tag: &'static str, indicators: &str) -> Self {
+        let mut chars = indicators.chars();
+
+        Self {
+            tag,
+            indicators: [chars.next().unwrap_or(' '), chars.next().unwrap_or(' ')],
+            subfields: vec![],
+        }
+    }
+
+    /// Add a subfield, unless its value is empty
+    fn subfield<T: AsRef<str>>(mut self, code: char, value: T) -> Self {
+        let value = value.as_ref().trim();
+
+        if !value.is_empty() {
+            self.subfields.push(Subfield {
+                code,
+                value: value.to_string(),
+            });
+        }
+        self
+    }
+
+    fn optional<T: AsRef<str>>(self, code: char, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.subfield(code, value),
+            None => self,
+        }
+    }
+
+    /// `$3` link to an authority record
+    fn authority(self, ppn: Option<Ppn>) -> Self {
+        self.optional('3', ppn.as_ref().map(Ppn::as_str))
+    }
+
+    /// Content of the field in ISO 2709: indicators and delimited subfields, ended by the field terminator
+    fn data(&self) -> Vec<u8> {
+        let mut data = String::new();
+        data.extend(self.indicators.iter());
+
+        let mut data = data.into_bytes();
+        for subfield in &self.subfields {
+            data.push(SUBFIELD_DELIMITER);
+            data.extend(subfield.code.to_string().as_bytes());
+            data.extend(subfield.value.as_bytes());
+        }
+        data.push(FIELD_TERMINATOR);
+
+        data
+    }
+}
+
+/// Notice bibliographique UNIMARC d'une thèse
+///
+/// The record holds the NNT (029), the coded data (100, 101), the title and statement of responsibility (200), the
+/// dissertation note (328), the abstracts (330), the translated titles (541), the Rameau subjects (6xx), the
+/// persons and organizations with their `$3` IdRef links (7xx) and the URLs of the electronic editions (856).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct Record {
+    fields: Vec<Field>,
+}
+
+impl Record {
+    pub fn from_mets(mets: &Mets) -> Result<Self, Error> {
+        let (record, admin) = metadata(mets)?;
+        let mut fields = vec![];
+
+        if let Ok(nnt) = Nnt::from_mets(mets) {
+            fields.push(
+                Field::new("029", "  ")
+                    .subfield('a', "FR")
+                    .subfield('b', nnt.as_str()),
+            );
+        }
+
+        fields.push(Field::new("100", "  ").subfield('a', general_data(mets, admin)));
+
+        let languages = distinct(
+            record
+                .language()
+                .iter()
+                .map(|language| language_code(language.text())),
+        );
+        let field = languages
+            .iter()
+            .fold(Field::new("101", "0 "), |field, code| {
+                field.subfield('a', code)
+            });
+        fields.push(field);
+
+        fields.push(title(record, admin));
+        fields.push(dissertation_note(admin));
+
+        for r#abstract in record.r#abstract().iter().flatten() {
+            fields.push(Field::new("330", "  ").subfield('a', r#abstract.text()));
+        }
+
+        for alternative in record.alternative().iter().flatten() {
+            fields.push(
+                Field::new("541", "1 ")
+                    .subfield('a', alternative.text())
+                    .subfield('z', language_code(alternative.lang())),
+            );
+        }
+
+        let vedettes = record
+            .sujet_rameau()
+            .iter()
+            .flat_map(|sujet_rameau| sujet_rameau.values())
+            .flatten();
+        fields.extend(vedettes.map(subject));
+
+        for (role, person) in admin.persons() {
+            let (tag, relator) = match role {
+                Role::Author => ("700", "070"),
+                Role::Director => ("701", "727"),
+                Role::President => ("701", "956"),
+                Role::Member => ("701", "555"),
+                Role::Rapporteur => ("701", "958"),
+            };
+            fields.push(
+                Field::new(tag, " 1")
+                    .authority(person.ppn())
+                    .subfield('a', &person.nom().0)
+                    .subfield('b', &person.prenom().0)
+                    .subfield('4', relator),
+            );
+        }
+
+        let organizations = admin
+            .thesis_degree()
+            .grantor()
+            .iter()
+            .map(|grantor| (grantor as &dyn Organization, "295"))
+            .chain(
+                admin
+                    .ecole_doctorale()
+                    .iter()
+                    .map(|ecole| (ecole as &dyn Organization, "996")),
+            )
+            .chain(
+                admin
+                    .partenaire_recherche()
+                    .iter()
+                    .map(|partenaire| (partenaire as &dyn Organization, "981")),
+            );
+        for (organization, relator) in organizations {
+            fields.push(
+                Field::new("712", "02")
+                    .authority(organization.ppn())
+                    .subfield('a', &organization.nom().0)
+                    .subfield('4', relator),
+            );
+        }
+
+        for url in edition_urls(mets) {
+            fields.push(Field::new("856", "4 ").subfield('u', url));
+        }
+
+        Ok(Self { fields })
+    }
+
+    /// Record in the ISO 2709 exchange format, encoded in UTF-8
+    pub fn to_iso2709(&self) -> Result<Vec<u8>, Error> {
+        let data: Vec<Vec<u8>> = self.fields.iter().map(Field::data).collect();
+        let (length, base) = lengths(&data)?;
+
+        let mut directory = String::new();
+        let mut start = 0;
+        for (field, data) in self.fields.iter().zip(&data) {
+            directory.push_str(&format!("{}{:04}{:05}", field.tag, data.len(), start));
+            start += data.len();
+        }
+
+        let mut record = leader(length, base).into_bytes();
+        record.extend(directory.as_bytes());
+        record.push(FIELD_TERMINATOR);
+        data.into_iter().for_each(|data| record.extend(data));
+        record.push(RECORD_TERMINATOR);
+
+        Ok(record)
+    }
+
+    /// Write the record as a MARCXML `record` document
+    pub fn write_marcxml<W: Write>(&self, writer: W) -> Result<(), Error> {
+        let data: Vec<Vec<u8>> = self.fields.iter().map(Field::data).collect();
+        let (length, base) = lengths(&data)?;
+
+        let mut w = XmlWriter::new(writer);
+        w.declaration()?;
+        w.element(
+            "record",
+            &[
+                ("xmlns", Some(MARCXML_NAMESPACE)),
+                ("type", Some("Bibliographic")),
+            ],
+            |w| {
+                w.text("leader", &[], &leader(length, base))?;
+
+                for field in &self.fields {
+                    let ind1 = field.indicators[0].to_string();
+                    let ind2 = field.indicators[1].to_string();
+                    let attributes = [
+                        ("tag", Some(field.tag)),
+                        ("ind1", Some(ind1.as_str())),
+                        ("ind2", Some(ind2.as_str())),
+                    ];
+
+                    w.element("datafield", &attributes, |w| {
+                        for subfield in &field.subfields {
+                            let code = subfield.code.to_string();
+                            w.text("subfield", &[("code", Some(&code))], &subfield.value)?;
+                        }
+                        Ok(())
+                    })?;
+                }
+
+                Ok(())
+            },
+        )?;
+        w.into_inner().flush()?;
+
+        Ok(())
+    }
+}
+
+/// Convert a TEF record to a UNIMARC record in ISO 2709
+pub fn to_unimarc(mets: &Mets) -> Result<Vec<u8>, Error> {
+    Record::from_mets(mets)?.to_iso2709()
+}
+
+/// Convert a TEF record to a UNIMARC record in MARCXML
+pub fn to_marcxml_string(mets: &Mets) -> Result<String, Error> {
+    let mut buffer = Vec::new();
+    write_marcxml(mets, &mut buffer)?;
+
+    into_string(buffer)
+}
+
+/// Write a TEF record as a UNIMARC record in MARCXML
+pub fn write_marcxml<W: Write>(mets: &Mets, writer: W) -> Result<(), Error> {
+    Record::from_mets(mets)?.write_marcxml(writer)
+}
+
+/// Length of the record and base address of its data, checked against the limits of ISO 2709
+fn lengths(data: &[Vec<u8>]) -> Result<(usize, usize), Error> {
+    if let Some(data) = data.iter().find(|data| data.len() > 9999) {
+        return Err(export_error(format!(
+            "field of {} bytes, at most 9999 are allowed",
+            data.len()
+        )));
+    }
+
+    let base = 24 + 12 * data.len() + 1;
+    let length = base + data.iter().map(Vec::len).sum::<usize>() + 1;
+    if length > 99999 {
+        return Err(export_error(format!(
+            "record of {} bytes, at most 99999 are allowed",
+            length
+        )));
+    }
+
+    Ok((length, base))
+}
+
+fn export_error(reason: String) -> Error {
+    Error::Export {
+        format: "UNIMARC",
+        reason,
+    }
+}
+
+/// Leader of a new monograph, printed text, with partial ISBD cataloguing
+fn leader(length: usize, base: usize) -> String {
+    format!("{:05}nam0 22{:05} i 450 ", length, base)
+}
+
+/// Coded data of the 100 `$a`: entry date, date of the defense, French cataloguing and Unicode
+fn general_data(mets: &Mets, admin: &ThesisAdmin) -> String {
+    let accepted = admin.date_accepted().date().ok();
+    let year = accepted
+        .as_ref()
+        .map(|date| format!("{:04}", date.year()))
+        .unwrap_or_else(|| "    ".to_string());
+
+    let created: Option<String> = mets.values().iter().find_map(|value| match value {
+        MetsValues::MetsHdr(header) => header.createdate().as_deref().map(|date| {
+            date.chars()
+                .filter(char::is_ascii_digit)
+                .take(8)
+                .collect::<String>()
+        }),
+        _ => None,
+    });
+    let entered = created
+        .filter(|date| date.len() == 8)
+        .or_else(|| {
+            accepted.map(|date| {
+                format!(
+                    "{:04}{:02}{:02}",
+                    date.year(),
+                    date.month().unwrap_or(1),
+                    date.day().unwrap_or(1)
+                )
+            })
+        })
+        .unwrap_or_else(|| "        ".to_string());
+
+    format!("{}d{}    k  y0frey50      ba", entered, year)
+}
+
+/// Bibliographic code of a language, `und` when it is unknown
+fn language_code(code: &str) -> String {
+    let code = code.trim().to_lowercase();
+    let primary = code.split(['-', '_']).next().unwrap_or_default();
+
+    match primary.len() {
+        3 => primary.to_string(),
+        _ => LANGUAGES
+            .iter()
+            .find(|(two, _)| *two == primary)
+            .map_or("und", |(_, three)| three)
+            .to_string(),
+    }
+}
+
+/// Title, author and directors
+fn title(record: &ThesisRecord, admin: &ThesisAdmin) -> Field {
+    let directors: Vec<String> = admin
+        .directeur_these()
+        .iter()
+        .map(|directeur| directeur.display_name())
+        .collect();
+
+    Field::new("200", "1 ")
+        .subfield('a', record.title().text())
+        .subfield('f', admin.auteur().display_name())
+        .optional(
+            'g',
+            (!directors.is_empty())
+                .then(|| format!("sous la direction de {}", directors.join(" et de "))),
+        )
+}
+
+/// Kind of thesis, discipline, grantors and year of the defense
+fn dissertation_note(admin: &ThesisAdmin) -> Field {
+    let degree = admin.thesis_degree();
+    let kind = thesis_type(degree.level().text());
+    let year = admin
+        .date_accepted()
+        .date()
+        .ok()
+        .map(|date| date.year().to_string());
+
+    let field = Field::new("328", " 0")
+        .subfield('b', kind)
+        .subfield('c', degree.discipline().text());
+    degree
+        .grantor()
+        .iter()
+        .fold(field, |field, grantor| {
+            field.subfield('e', &grantor.nom().0)
+        })
+        .optional('d', year)
+}
+
+/// Rameau heading, with the `$3` links of its entry element and subdivisions
+fn subject(vedette: &SujetRameauValues) -> Field {
+    let (tag, indicators) = match vedette {
+        SujetRameauValues::VedetteRameauPersonne(_) => ("600", " 1"),
+        SujetRameauValues::VedetteRameauCollectivite(_) => ("601", "02"),
+        SujetRameauValues::VedetteRameauFamille(_) => ("602", "  "),
+        SujetRameauValues::VedetteRameauAuteurTitre(_) => ("604", "  "),
+        SujetRameauValues::VedetteRameauTitre(_) => ("605", "  "),
+        SujetRameauValues::VedetteRameauNomCommun(_) => ("606", "  "),
+        SujetRameauValues::VedetteRameauNomGeographique(_) => ("607", "  "),
+        SujetRameauValues::VedetteRameauGenreForme(_) => ("608", "  "),
+    };
+
+    let entree = vedette.element_entree();
+    let field = Field::new(tag, indicators)
+        .authority(ppn(entree.autorite_source(), entree.autorite_externe()))
+        .subfield('a', entree.text());
+
+    vedette
+        .subdivisions()
+        .iter()
+        .fold(field, |field, subdivision| {
+            let code = match subdivision.r#type().trim() {
+                "subdivisionDeForme" => 'j',
+                "subdivisionGeographique" => 'y',
+                "subdivisionChronologique" => 'z',
+                _ => 'x',
+            };
+            field
+                .authority(ppn(
+                    subdivision.autorite_source(),
+                    subdivision.autorite_externe(),
+                ))
+                .subfield(code, subdivision.text())
+        })
+        .subfield('2', "rameau")
+}
+
+/// PPN of a Rameau authority record, the source defaults to the Sudoc
+fn ppn(source: &Option<String>, id: &Option<String>) -> Option<Ppn> {
+    let source = source.as_deref().unwrap_or("sudoc").trim().to_lowercase();
+
+    match PPN_SOURCES.contains(&source.as_str()) {
+        true => id.as_deref()?.parse().ok(),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::THESE;
+
+    /// Tag and data of each field, read through the directory
+    fn fields(record: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let length: usize = std::str::from_utf8(&record[..5]).unwrap().parse().unwrap();
+        let base: usize = std::str::from_utf8(&record[12..17])
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(length, record.len());
+        assert_eq!(record[base - 1], FIELD_TERMINATOR);
+        assert_eq!(record[length - 1], RECORD_TERMINATOR);
+
+        let directory = &record[24..base - 1];
+        assert_eq!(directory.len() % 12, 0);
+
+        let mut end = 0;
+        let fields = directory
+            .chunks(12)
+            .map(|entry| {
+                let entry = std::str::from_utf8(entry).unwrap();
+                let length: usize = entry[3..7].parse().unwrap();
+                let start: usize = entry[7..].parse().unwrap();
+                assert_eq!(start, end);
+                end = start + length;
+
+                let data = &record[base + start..base + end];
+                assert_eq!(data.last(), Some(&FIELD_TERMINATOR));
+                (entry[..3].to_string(), data.to_vec())
+            })
+            .collect();
+        assert_eq!(base + end + 1, length);
+
+        fields
+    }
+
+    fn subfield(data: &[u8], code: char) -> Option<String> {
+        data[..data.len() - 1]
+            .split(|byte| *byte == SUBFIELD_DELIMITER)
+            .skip(1)
+            .map(|subfield| String::from_utf8(subfield.to_vec()).unwrap())
+            .find(|subfield| subfield.starts_with(code))
+            .map(|subfield| subfield[1..].to_string())
+    }
+
+    #[test]
+    fn iso2709() {
+        let record = to_unimarc(&parse_tef(THESE).unwrap()).unwrap();
+        assert_eq!(&record[5..12], b"nam0 22");
+        assert_eq!(&record[17..24], b" i 450 ");
+
+        let fields = fields(&record);
+        let field = |tag: &str| -> Vec<&[u8]> {
+            fields
+                .iter()
+                .filter(|(other, _)| other == tag)
+                .map(|(_, data)| data.as_slice())
+                .collect()
+        };
+
+        assert_eq!(subfield(field("029")[0], 'b').unwrap(), "2019LYSE1234");
+
+        let general_data = subfield(field("100")[0], 'a').unwrap();
+        assert_eq!(general_data.len(), 36);
+        assert_eq!(general_data, "20191105d2019    k  y0frey50      ba");
+
+        let author = field("700")[0];
+        assert_eq!(&author[..2], b" 1");
+        assert_eq!(subfield(author, '3').unwrap(), "123456789");
+        assert_eq!(subfield(author, '4').unwrap(), "070");
+
+        let links: Vec<Option<String>> = field("701")
+            .into_iter()
+            .map(|person| subfield(person, '3'))
+            .collect();
+        assert_eq!(links, [Some("027189848".to_string()), None, None, None]);
+
+        let grantor = field("712")[0];
+        assert_eq!(subfield(grantor, '3').unwrap(), "137964781");
+        assert_eq!(subfield(grantor, 'a').unwrap(), "Université de Lyon");
+    }
+}
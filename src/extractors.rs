@@ -8,41 +8,9 @@ use crate::gestion::star::StarGestion;
 #[cfg(feature = "gestion")]
 use crate::gestion::step::StepGestion;
 
-use thiserror::Error;
-
-#[derive(Debug, Error)]
-pub enum TefError {
-    #[error("date accetped not found")]
-    DateAcceptedNotFound,
-    #[error("thesis degree discipline not found")]
-    ThesisDegreeDisciplineNotFound,
-    #[error("thesis degree grantor not found")]
-    ThesisDegreeGrantorNotFound,
-    #[error("theses sur travaux not found")]
-    TheseSurTravauxNotFound,
-    #[error("auteur not found")]
-    AuteurNotFound,
-    #[error("directeur de thèse not found")]
-    DirecteurTheseNotFound,
-    #[error("oai set not found")]
-    OaiSetSpecNotFound,
-    #[error("title not found")]
-    TitleNotFound,
-    #[error("alternative title not found")]
-    AlternativeTitleNotFound,
-    #[error("abstract not found")]
-    AbstractNotFound,
-    #[error("subject not found")]
-    SubjectNotFound,
-    #[error("language not found")]
-    LanguageNotFound,
-    #[error("element entree not found")]
-    ElementdEntreeNotFound,
-    #[error("star getsion not found")]
-    StarGestionNotFound,
-    #[error("step getsion not found")]
-    StepGestionNotFound,
-}
+use crate::edit::{THESIS_ADMIN, THESIS_RECORD};
+use crate::error::Error;
+use crate::identifiers::Nnt;
 
 macro_rules! from_option {
     ($type:ty) => {
@@ -138,7 +106,7 @@ impl<'a> From<&'a Mets> for Vec<&'a ThesisRecord> {
 }
 
 impl<'a> TryFrom<&'a Mets> for &'a DateAccepted {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -151,7 +119,7 @@ impl<'a> TryFrom<&'a Mets> for &'a DateAccepted {
 
         match result.first() {
             Some(date) => Ok(date),
-            None => Err(TefError::DateAcceptedNotFound),
+            None => Err(Error::missing(format!("{THESIS_ADMIN}/dateAccepted"))),
         }
     }
 }
@@ -160,7 +128,7 @@ from_option!(&'a DateAccepted);
 
 /// Implémentation qui respecte la cardinalité du TEF
 impl<'a> TryFrom<&'a Mets> for Vec<&'a Identifier> {
-    type Error = &'static str;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -170,7 +138,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Identifier> {
             .collect::<Vec<&_>>();
 
         if result.is_empty() {
-            Err("Failed to get data")
+            Err(Error::missing(format!("{THESIS_ADMIN}/identifier")))
         } else {
             Ok(result)
         }
@@ -180,7 +148,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Identifier> {
 from_option!(Vec<&'a Identifier>);
 
 impl<'a> TryFrom<&'a Mets> for &'a ThesisDegreeDiscipline {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -194,7 +162,9 @@ impl<'a> TryFrom<&'a Mets> for &'a ThesisDegreeDiscipline {
 
         match result.first() {
             Some(discipline) => Ok(discipline),
-            None => Err(TefError::ThesisDegreeDisciplineNotFound),
+            None => Err(Error::missing(format!(
+                "{THESIS_ADMIN}/thesis.degree/thesis.degree.discipline"
+            ))),
         }
     }
 }
@@ -202,7 +172,7 @@ impl<'a> TryFrom<&'a Mets> for &'a ThesisDegreeDiscipline {
 from_option!(&'a ThesisDegreeDiscipline);
 
 impl<'a> TryFrom<&'a Mets> for Vec<&'a ThesisDegreeGrantor> {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -217,7 +187,9 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a ThesisDegreeGrantor> {
         if !result.is_empty() {
             Ok(result)
         } else {
-            Err(TefError::ThesisDegreeGrantorNotFound)
+            Err(Error::missing(format!(
+                "{THESIS_ADMIN}/thesis.degree/thesis.degree.grantor"
+            )))
         }
     }
 }
@@ -225,7 +197,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a ThesisDegreeGrantor> {
 from_option!(Vec<&'a ThesisDegreeGrantor>);
 
 impl<'a> TryFrom<&'a Mets> for &'a TheseSurTravaux {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -240,10 +212,10 @@ impl<'a> TryFrom<&'a Mets> for &'a TheseSurTravaux {
                 if elem != &&TheseSurTravaux::default() {
                     Ok(elem)
                 } else {
-                    Err(TefError::TheseSurTravauxNotFound)
+                    Err(Error::missing(format!("{THESIS_ADMIN}/theseSurTravaux")))
                 }
             }
-            None => Err(TefError::TheseSurTravauxNotFound),
+            None => Err(Error::missing(format!("{THESIS_ADMIN}/theseSurTravaux"))),
         }
     }
 }
@@ -251,7 +223,7 @@ impl<'a> TryFrom<&'a Mets> for &'a TheseSurTravaux {
 from_option!(&'a TheseSurTravaux);
 
 impl<'a> TryFrom<&'a Mets> for &'a Auteur {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -263,7 +235,7 @@ impl<'a> TryFrom<&'a Mets> for &'a Auteur {
 
         match result.first() {
             Some(elem) => Ok(elem),
-            None => Err(TefError::AuteurNotFound),
+            None => Err(Error::missing(format!("{THESIS_ADMIN}/auteur"))),
         }
     }
 }
@@ -271,7 +243,7 @@ impl<'a> TryFrom<&'a Mets> for &'a Auteur {
 from_option!(&'a Auteur);
 
 impl<'a> TryFrom<&'a Mets> for Vec<&'a DirecteurThese> {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -284,7 +256,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a DirecteurThese> {
         if !result.is_empty() {
             Ok(result)
         } else {
-            Err(TefError::DirecteurTheseNotFound)
+            Err(Error::missing(format!("{THESIS_ADMIN}/directeurThese")))
         }
     }
 }
@@ -375,7 +347,7 @@ impl<'a> From<&'a Mets> for Option<Vec<&'a PartenaireRecherche>> {
 }
 
 impl<'a> TryFrom<&'a Mets> for Vec<&'a OaiSetSpec> {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
@@ -388,7 +360,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a OaiSetSpec> {
         if !result.is_empty() {
             Ok(result)
         } else {
-            Err(TefError::OaiSetSpecNotFound)
+            Err(Error::missing(format!("{THESIS_ADMIN}/oaiSetSpec")))
         }
     }
 }
@@ -396,7 +368,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a OaiSetSpec> {
 from_option!(Vec<&'a OaiSetSpec>);
 
 impl<'a> TryFrom<&'a Mets> for &'a Title {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_records: Vec<&ThesisRecord> = Vec::from(value);
@@ -409,7 +381,7 @@ impl<'a> TryFrom<&'a Mets> for &'a Title {
 
         match result.first() {
             Some(title) => Ok(title),
-            None => Err(TefError::TitleNotFound),
+            None => Err(Error::missing(format!("{THESIS_RECORD}/title"))),
         }
     }
 }
@@ -417,7 +389,7 @@ impl<'a> TryFrom<&'a Mets> for &'a Title {
 from_option!(&'a Title);
 
 impl<'a> TryFrom<&'a Mets> for Vec<&'a Alternative> {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_records: Vec<&ThesisRecord> = Vec::from(value);
@@ -432,7 +404,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Alternative> {
         if !result.is_empty() {
             Ok(result)
         } else {
-            Err(TefError::AlternativeTitleNotFound)
+            Err(Error::missing(format!("{THESIS_RECORD}/alternative")))
         }
     }
 }
@@ -440,7 +412,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Alternative> {
 from_option!(Vec<&'a Alternative>);
 
 impl<'a> TryFrom<&'a Mets> for Vec<&'a Abstract> {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_records: Vec<&ThesisRecord> = Vec::from(value);
@@ -456,7 +428,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Abstract> {
         if !result.is_empty() {
             Ok(result)
         } else {
-            Err(TefError::AbstractNotFound)
+            Err(Error::missing(format!("{THESIS_RECORD}/abstract")))
         }
     }
 }
@@ -464,7 +436,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Abstract> {
 from_option!(Vec<&'a Abstract>);
 
 impl<'a> TryFrom<&'a Mets> for Vec<&'a Subject> {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_records: Vec<&ThesisRecord> = Vec::from(value);
@@ -480,7 +452,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Subject> {
         if !result.is_empty() {
             Ok(result)
         } else {
-            Err(TefError::SubjectNotFound)
+            Err(Error::missing(format!("{THESIS_RECORD}/subject")))
         }
     }
 }
@@ -488,7 +460,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Subject> {
 from_option!(Vec<&'a Subject>);
 
 impl<'a> TryFrom<&'a Mets> for Vec<&'a Language> {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_records: Vec<&ThesisRecord> = Vec::from(value);
@@ -502,7 +474,7 @@ impl<'a> TryFrom<&'a Mets> for Vec<&'a Language> {
         if !result.is_empty() {
             Ok(result)
         } else {
-            Err(TefError::LanguageNotFound)
+            Err(Error::missing(format!("{THESIS_RECORD}/language")))
         }
     }
 }
@@ -525,7 +497,7 @@ impl<'a> From<&'a Mets> for Option<&'a SujetRameau> {
 }
 
 impl TryFrom<&SujetRameauValues> for ElementdEntree {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &SujetRameauValues) -> Result<Self, Self::Error> {
         let element_entree = match value {
@@ -542,7 +514,7 @@ impl TryFrom<&SujetRameauValues> for ElementdEntree {
         if element_entree != ElementdEntree::default() {
             Ok(element_entree)
         } else {
-            Err(TefError::ElementdEntreeNotFound)
+            Err(Error::missing("sujetRameau/elementdEntree"))
         }
     }
 }
@@ -579,7 +551,7 @@ impl From<&SujetRameauValues> for Option<Vec<Subdivision>> {
 
 #[cfg(feature = "gestion")]
 impl<'a> TryFrom<&'a Mets> for &'a StarGestion {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_records: Vec<&DmdSec> = Vec::from(value);
@@ -595,7 +567,7 @@ impl<'a> TryFrom<&'a Mets> for &'a StarGestion {
 
         match result.first() {
             Some(result) => Ok(result),
-            None => Err(TefError::StarGestionNotFound),
+            None => Err(Error::missing("mets/dmdSec/mdWrap/xmlData/star_gestion")),
         }
     }
 }
@@ -605,7 +577,7 @@ from_option!(&'a StarGestion);
 
 #[cfg(feature = "gestion")]
 impl<'a> TryFrom<&'a Mets> for &'a StepGestion {
-    type Error = TefError;
+    type Error = Error;
 
     fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
         let thesis_records: Vec<&DmdSec> = Vec::from(value);
@@ -621,10 +593,33 @@ impl<'a> TryFrom<&'a Mets> for &'a StepGestion {
 
         match result.first() {
             Some(result) => Ok(result),
-            None => Err(TefError::StepGestionNotFound),
+            None => Err(Error::missing("mets/dmdSec/mdWrap/xmlData/step_gestion")),
         }
     }
 }
 
 #[cfg(feature = "gestion")]
 from_option!(&'a StepGestion);
+
+/// Every person of the record with their role
+impl<'a> From<&'a Mets> for Vec<(Role, &'a dyn Person)> {
+    fn from(value: &'a Mets) -> Self {
+        let thesis_admins: Vec<&ThesisAdmin> = Vec::from(value);
+
+        thesis_admins
+            .into_iter()
+            .flat_map(|admin| admin.persons())
+            .collect()
+    }
+}
+
+/// NNT of the record, see [`Nnt::from_mets`]
+impl<'a> TryFrom<&'a Mets> for Nnt {
+    type Error = Error;
+
+    fn try_from(value: &'a Mets) -> Result<Self, Self::Error> {
+        Nnt::from_mets(value)
+    }
+}
+
+from_option!(Nnt);
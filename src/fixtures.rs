@@ -0,0 +1,20 @@
+//! The thesis used by the unit tests (`tests/data/these.xml`).
+
+pub(crate) const THESE: &str = include_str!("../tests/data/these.xml");
+
+/// The fixture with each `(from, to)` replacement applied in order.
+pub(crate) fn these_with(replacements: &[(&str, &str)]) -> String {
+    replacements
+        .iter()
+        .fold(THESE.to_string(), |these, (from, to)| {
+            these.replace(from, to)
+        })
+}
+
+/// The fixture with a `tef:editeur` appended to the edition.
+pub(crate) fn these_with_editeur(editeur: &str) -> String {
+    these_with(&[(
+        "</tef:edition>",
+        &format!("<tef:editeur>{editeur}</tef:editeur></tef:edition>"),
+    )])
+}
@@ -1,10 +1,16 @@
-use getset::Getters;
+use getset::{Getters, MutGetters, Setters};
+#[cfg(feature = "lax")]
 use lax_derive::lax;
 use serde::{Deserialize, Serialize};
 
+use crate::date::TefDate;
+use crate::error::Error;
+
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct StarGestion {
     #[serde(rename = "@codeEtab")]
     code_etab: String,
@@ -25,8 +31,10 @@ pub struct StarGestion {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Traitements {
     #[serde(rename = "@scenario")]
     scenario: String,
@@ -44,38 +52,52 @@ pub struct Traitements {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Entree;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Step;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Maj;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Facile;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct RemonteeArchive;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Invalidation;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Sorties {
     #[serde(rename = "@date")]
     date: String,
@@ -85,8 +107,10 @@ pub struct Sorties {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Cines {
     #[serde(rename = "@numeroPAC")]
     numero_pac: String,
@@ -98,14 +122,24 @@ pub struct Cines {
     trace: String,
 }
 
+impl Cines {
+    pub fn date(&self) -> Result<TefDate, Error> {
+        self.date_cines.parse()
+    }
+}
+
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Sudoc;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Diffusion {
     #[serde(rename = "@urlPerenne")]
     url_perenne: String,
@@ -123,17 +157,30 @@ pub struct Diffusion {
     confidentialite_fin: String,
 }
 
+impl Diffusion {
+    /// End of the embargo (`@embargoFin`)
+    pub fn date(&self) -> Result<TefDate, Error> {
+        self.embargo_fin.parse()
+    }
+}
+
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Workflow;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct CtrlUrl;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Purge;
@@ -1,10 +1,13 @@
-use getset::Getters;
+use getset::{Getters, MutGetters, Setters};
+#[cfg(feature = "lax")]
 use lax_derive::lax;
 use serde::{Deserialize, Serialize};
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct StepGestion {
     #[serde(rename = "@codeEtab")]
     code_etab: String,
@@ -23,8 +26,10 @@ pub struct StepGestion {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Traitements {
     entree: Entree,
     maj: Maj,
@@ -32,23 +37,31 @@ pub struct Traitements {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Maj;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Workflow;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Entree;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Sorties {
     nnt: Nnt,
     star: Star,
@@ -57,18 +70,24 @@ pub struct Sorties {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Diffusion;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Sudoc;
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Nnt {
     #[serde(rename = "@dateNnt")]
     date_nnt: Option<String>,
@@ -83,6 +102,8 @@ pub struct Nnt {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Star;
@@ -0,0 +1,262 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Serialize, Serializer};
+
+use crate::error::Error;
+use crate::mets::*;
+use crate::tef::ThesisAdmin;
+
+/// `xsi:type` of the `dc:identifier` holding the NNT
+pub const NNT_TYPE: &str = "tef:NNT";
+
+/// Numéro national de thèse
+///
+/// 12 characters: the year of the defense (or of the first registration) on 4 digits, the code of the
+/// establishment on 4 letters or digits, e.g. `LYSE` or `PA01`, and a sequence number on 4 letters or digits,
+/// e.g. `2019LYSE1234`. Letters are read case-insensitively and stored in upper case.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Nnt(String);
+
+impl Nnt {
+    pub fn year(&self) -> u16 {
+        self.0[..4].parse().unwrap_or_default()
+    }
+
+    /// Code of the establishment
+    pub fn establishment(&self) -> &str {
+        &self.0[4..8]
+    }
+
+    /// Sequence number within the establishment and the year
+    pub fn sequence(&self) -> &str {
+        &self.0[8..]
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Nnt {
+    /// NNT of a record: the `dc:identifier` of type `tef:NNT` of the thesis admin data, see
+    /// [`Mets::thesis_admin`], or else the NNT of the STEP management data
+    pub fn from_mets(mets: &Mets) -> Result<Self, Error> {
+        if let Some(nnt) = mets.thesis_admin().and_then(ThesisAdmin::nnt) {
+            return nnt.parse();
+        }
+
+        #[cfg(feature = "gestion")]
+        for value in mets.values() {
+            if let MetsValues::DmdSec(dmd_sec) = value {
+                if let XmlDataValues::StepGestion(step) = dmd_sec.value().value().value() {
+                    let nnt = step.traitements().sorties().nnt().text();
+
+                    if !nnt.trim().is_empty() {
+                        return nnt.parse();
+                    }
+                }
+            }
+        }
+
+        Err(Error::missing(format!(
+            "mets/amdSec/techMD/mdWrap/xmlData/thesisAdmin/identifier[@type={}]",
+            NNT_TYPE
+        )))
+    }
+}
+
+impl FromStr for Nnt {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let nnt = value.trim().to_uppercase();
+
+        if nnt.len() != 12 || !nnt.is_ascii() {
+            return Err(invalid("NNT", value, "expected 12 characters"));
+        }
+        if !nnt[..4].bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("NNT", value, "expected a year on 4 digits"));
+        }
+        if !nnt[4..].bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(invalid(
+                "NNT",
+                value,
+                "expected an establishment code and a sequence number made of letters and digits",
+            ));
+        }
+
+        Ok(Self(nnt))
+    }
+}
+
+impl Display for Nnt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Nnt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// Sources of the `tef:autoriteExterne` holding a PPN
+pub const PPN_SOURCES: [&str; 2] = ["sudoc", "idref"];
+
+/// Identifiant d'une notice du Sudoc ou d'IdRef
+///
+/// 8 digits followed by a check character, a digit or `X`, computed modulo 11 with the weights 9 to 2, e.g.
+/// `026403447`. The IdRef URI of the record is also accepted when parsing.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ppn(String);
+
+impl Ppn {
+    /// URI of the authority record
+    pub fn idref_uri(&self) -> String {
+        format!("https://www.idref.fr/{}", self.0)
+    }
+
+    /// IRI of the entity described by the authority record, as used in the IdRef linked data
+    pub fn idref_iri(&self) -> String {
+        format!("http://www.idref.fr/{}/id", self.0)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Check character of the first 8 digits
+    fn check_char(digits: &[u8]) -> char {
+        let sum: u32 = digits
+            .iter()
+            .zip((2..=9).rev())
+            .map(|(digit, weight)| u32::from(digit - b'0') * weight)
+            .sum();
+
+        match (11 - sum % 11) % 11 {
+            10 => 'X',
+            check => char::from_digit(check, 10).unwrap_or('0'),
+        }
+    }
+}
+
+impl FromStr for Ppn {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let ppn = value.trim();
+        let ppn = ["https://www.idref.fr/", "http://www.idref.fr/"]
+            .iter()
+            .find_map(|prefix| ppn.strip_prefix(prefix))
+            .unwrap_or(ppn)
+            .to_uppercase();
+        let bytes = ppn.as_bytes();
+
+        if bytes.len() != 9 {
+            return Err(invalid("PPN", value, "expected 9 characters"));
+        }
+        if !bytes[..8].iter().all(u8::is_ascii_digit) {
+            return Err(invalid(
+                "PPN",
+                value,
+                "expected 8 digits and a check character",
+            ));
+        }
+
+        let check = Self::check_char(&bytes[..8]);
+        if char::from(bytes[8]) != check {
+            return Err(invalid("PPN", value, "wrong check character"));
+        }
+
+        Ok(Self(ppn))
+    }
+}
+
+impl Display for Ppn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Ppn {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+fn invalid(kind: &'static str, value: &str, reason: &'static str) -> Error {
+    Error::InvalidIdentifier {
+        kind,
+        value: value.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nnt_parts() {
+        let nnt: Nnt = "2019LYSE1234".parse().unwrap();
+
+        assert_eq!(nnt.year(), 2019);
+        assert_eq!(nnt.establishment(), "LYSE");
+        assert_eq!(nnt.sequence(), "1234");
+    }
+
+    #[test]
+    fn nnt_lowercase() {
+        let nnt: Nnt = " 2019pa01a012 ".parse().unwrap();
+
+        assert_eq!(nnt.as_str(), "2019PA01A012");
+        assert_eq!(nnt.establishment(), "PA01");
+        assert_eq!(nnt.sequence(), "A012");
+    }
+
+    #[test]
+    fn nnt_from_mets() {
+        let mets = crate::parse_tef(crate::fixtures::THESE).unwrap();
+
+        assert_eq!(Nnt::from_mets(&mets).unwrap().as_str(), "2019LYSE1234");
+    }
+
+    #[test]
+    fn nnt_invalid() {
+        assert!("2019LYSE123".parse::<Nnt>().is_err());
+        assert!("2019LYSE12345".parse::<Nnt>().is_err());
+        assert!("19LYSE123456".parse::<Nnt>().is_err());
+        assert!("2019LYSE-234".parse::<Nnt>().is_err());
+        assert!("2019LYSÉ123".parse::<Nnt>().is_err());
+    }
+
+    #[test]
+    fn ppn_check_digit() {
+        assert_eq!("026403447".parse::<Ppn>().unwrap().as_str(), "026403447");
+        assert!("026403448".parse::<Ppn>().is_err());
+    }
+
+    #[test]
+    fn ppn_check_x() {
+        assert_eq!("02718983x".parse::<Ppn>().unwrap().as_str(), "02718983X");
+        assert!("027189830".parse::<Ppn>().is_err());
+    }
+
+    #[test]
+    fn ppn_invalid() {
+        assert!("02640344".parse::<Ppn>().is_err());
+        assert!("0264034470".parse::<Ppn>().is_err());
+        assert!("X26403447".parse::<Ppn>().is_err());
+    }
+
+    #[test]
+    fn ppn_idref_uri() {
+        let ppn: Ppn = "https://www.idref.fr/026403447".parse().unwrap();
+
+        assert_eq!(ppn.as_str(), "026403447");
+        assert_eq!(ppn.idref_uri(), "https://www.idref.fr/026403447");
+        assert_eq!("http://www.idref.fr/026403447".parse::<Ppn>().unwrap(), ppn);
+    }
+}
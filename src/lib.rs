@@ -1,11 +1,29 @@
+use std::io::BufRead;
+
 use mets::Mets;
 
+pub mod date;
 pub mod dc;
 pub mod dcterms;
+mod de;
+pub mod diff;
+mod edit;
+mod encoding;
+pub mod error;
+pub mod export;
+#[cfg(test)]
+mod fixtures;
+pub mod identifiers;
 pub mod mads;
 pub mod mets;
 pub mod mets_rights;
+pub mod names;
+pub mod oai;
+pub mod raw;
+mod ser;
 pub mod tef;
+pub mod thesis;
+pub mod validation;
 
 #[cfg(feature = "extractors")]
 pub mod extractors;
@@ -13,7 +31,63 @@ pub mod extractors;
 #[cfg(feature = "gestion")]
 pub mod gestion;
 
+pub use date::{DatePrecision, TefDate};
+pub use de::{ParseMode, ParseOptions, ParseReport};
+pub use diff::{diff, Change, ChangeKind};
+pub use error::{Error, Location};
+pub use export::{
+    to_bibtex, to_csl_json, to_csl_json_string, to_json_ld, to_json_ld_string, to_marcxml_string,
+    to_ntriples, to_oai_dc_string, to_ris, to_turtle, to_unimarc, write_marcxml, write_oai_dc,
+};
+pub use identifiers::{Nnt, Ppn};
+pub use ser::{to_tef_string, write_tef};
+pub use thesis::Thesis;
+pub use validation::{check_references, validate, ValidationReport};
+
 /// Parse a tef file
-pub fn parse_tef(input: &str) -> Result<Mets, Box<dyn std::error::Error>> {
-    Ok(quick_xml::de::from_str(input)?)
+pub fn parse_tef(input: &str) -> Result<Mets, Error> {
+    parse_tef_with(input, &ParseOptions::default()).map(|(mets, _)| mets)
+}
+
+/// Parse a tef file with the given options.
+///
+/// In lenient mode, the missing mandatory elements are set to their default value. The returned report lists
+/// the defaulted fields and the elements the crate doesn't model.
+pub fn parse_tef_with(input: &str, options: &ParseOptions) -> Result<(Mets, ParseReport), Error> {
+    de::from_str(input, options)
+}
+
+/// Parse a tef file from raw bytes.
+///
+/// The encoding is taken from the BOM or the XML declaration (UTF-8 and ISO-8859-1 are supported).
+pub fn parse_tef_from_slice(input: &[u8]) -> Result<Mets, Error> {
+    parse_tef_from_slice_with(input, &ParseOptions::default()).map(|(mets, _)| mets)
+}
+
+/// Parse a tef file from raw bytes with the given options, see [`parse_tef_with`].
+pub fn parse_tef_from_slice_with(
+    input: &[u8],
+    options: &ParseOptions,
+) -> Result<(Mets, ParseReport), Error> {
+    let input = encoding::decode(input)?;
+
+    de::from_str(&input, options)
+}
+
+/// Parse a tef file from a reader (file, gzip stream, ...).
+///
+/// See [`parse_tef_from_slice`] for the supported encodings.
+pub fn parse_tef_from_reader<R: BufRead>(reader: R) -> Result<Mets, Error> {
+    parse_tef_from_reader_with(reader, &ParseOptions::default()).map(|(mets, _)| mets)
+}
+
+/// Parse a tef file from a reader with the given options, see [`parse_tef_with`].
+pub fn parse_tef_from_reader_with<R: BufRead>(
+    mut reader: R,
+    options: &ParseOptions,
+) -> Result<(Mets, ParseReport), Error> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+
+    parse_tef_from_slice_with(&buffer, options)
 }
@@ -1,4 +1,4 @@
-use getset::Getters;
+use getset::{Getters, MutGetters, Setters};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "lax")]
@@ -9,8 +9,10 @@ use lax_derive::lax;
 /// <http://www.loc.gov/mads/>
 /// Description d'une personne en texte libre
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Description {
     #[serde(rename = "$value")]
     value: String,
@@ -21,8 +23,10 @@ pub struct Description {
 /// <http://www.loc.gov/mads/>
 /// Partie du nom complet d'une personne
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct NamePart {
     #[serde(rename = "@type")]
     r#type: String,
@@ -0,0 +1,216 @@
+use super::*;
+use crate::edit::{THESIS_ADMIN, THESIS_RECORD};
+use crate::error::Error;
+
+/// Profile of the TEF documents
+pub const TEF_PROFILE: &str = "http://www.abes.fr/abes/documents/tef/recommandation/profil_tef.xml";
+
+const THESIS_DMDID: &str = "desc_these";
+const THESIS_ADMID: &str = "admin_these";
+const THESIS_DIV: &str = "these";
+const VERSION_DIV: &str = "version_complete";
+
+impl Mets {
+    pub fn builder() -> MetsBuilder {
+        MetsBuilder::default()
+    }
+}
+
+/// Builder of a TEF document holding the descriptive and administrative metadata of a thesis
+///
+/// The document has a `mets:metsHdr`, the `tef:thesisRecord` in the `mets:dmdSec` `desc_these`, the
+/// `tef:thesisAdmin` in the `mets:techMD` `admin_these`, and a logical `mets:structMap` where the `THESE` division
+/// points to both and holds a `VERSION_COMPLETE` division. The header needs at least one [`MetsBuilder::agent`].
+#[derive(Debug, Clone, Default)]
+pub struct MetsBuilder {
+    objid: Option<String>,
+    profile: Option<String>,
+    createdate: Option<String>,
+    lastmoddate: Option<String>,
+    recordstatus: Option<String>,
+    agents: Vec<Agent>,
+    thesis_record: Option<ThesisRecord>,
+    thesis_admin: Option<ThesisAdmin>,
+}
+
+impl MetsBuilder {
+    /// Identifier of the document, the NNT of the thesis by default
+    pub fn objid<T: Into<String>>(mut self, objid: T) -> Self {
+        self.objid = Some(objid.into());
+        self
+    }
+
+    /// Profile of the document, [`TEF_PROFILE`] by default
+    pub fn profile<T: Into<String>>(mut self, profile: T) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Creation date and time, e.g. `2019-11-05T10:00:00`
+    pub fn createdate<T: Into<String>>(mut self, createdate: T) -> Self {
+        self.createdate = Some(createdate.into());
+        self
+    }
+
+    /// Last modification date and time, e.g. `2019-12-01T12:30:00`
+    pub fn lastmoddate<T: Into<String>>(mut self, lastmoddate: T) -> Self {
+        self.lastmoddate = Some(lastmoddate.into());
+        self
+    }
+
+    pub fn recordstatus<T: Into<String>>(mut self, recordstatus: T) -> Self {
+        self.recordstatus = Some(recordstatus.into());
+        self
+    }
+
+    /// Add an agent of the document, e.g. `("CREATOR", "STAR")`
+    pub fn agent<R: Into<String>, N: Into<String>>(mut self, role: R, name: N) -> Self {
+        self.agents.push(Agent {
+            role: role.into(),
+            otherrole: None,
+            values: vec![AgentValues::Name(Name(name.into()))],
+        });
+        self
+    }
+
+    pub fn thesis_record(mut self, record: ThesisRecord) -> Self {
+        self.thesis_record = Some(record);
+        self
+    }
+
+    pub fn thesis_admin(mut self, admin: ThesisAdmin) -> Self {
+        self.thesis_admin = Some(admin);
+        self
+    }
+
+    pub fn build(self) -> Result<Mets, Error> {
+        let record = self
+            .thesis_record
+            .ok_or_else(|| Error::missing(THESIS_RECORD))?;
+        let admin = self
+            .thesis_admin
+            .ok_or_else(|| Error::missing(THESIS_ADMIN))?;
+
+        if self.agents.is_empty() {
+            return Err(Error::missing("mets/metsHdr/agent"));
+        }
+
+        let objid = self.objid.or_else(|| admin.nnt().map(str::to_string));
+
+        let header = MetsHdr {
+            createdate: self.createdate,
+            lastmoddate: self.lastmoddate,
+            recordstatus: self.recordstatus,
+            values: self.agents.into_iter().map(MetsHdrValues::Agent).collect(),
+        };
+
+        let dmd_sec = DmdSec {
+            id: THESIS_DMDID.to_string(),
+            created: None,
+            value: md_wrap("tef_desc_these", XmlDataValues::ThesisRecord(record)),
+        };
+
+        let amd_sec = AmdSec {
+            values: vec![AmdSecValues::TechMD(TechMD {
+                id: THESIS_ADMID.to_string(),
+                value: md_wrap("tef_admin_these", XmlDataValues::ThesisAdmin(admin)),
+            })],
+        };
+
+        let version = Div {
+            r#type: "VERSION_COMPLETE".to_string(),
+            id: Some(VERSION_DIV.to_string()),
+            ..Default::default()
+        };
+        let thesis = Div {
+            r#type: "THESE".to_string(),
+            dmdid: Some(THESIS_DMDID.to_string()),
+            amdid: Some(THESIS_ADMID.to_string()),
+            id: Some(THESIS_DIV.to_string()),
+            contentids: None,
+            values: vec![DivValues::Div(Box::new(version))],
+        };
+        let struct_map = StructMap {
+            r#type: "logical".to_string(),
+            value: vec![thesis],
+        };
+
+        Ok(Mets {
+            objid,
+            profile: Some(self.profile.unwrap_or_else(|| TEF_PROFILE.to_string())),
+            values: vec![
+                MetsValues::MetsHdr(header),
+                MetsValues::DmdSec(dmd_sec),
+                MetsValues::AmdSec(amd_sec),
+                MetsValues::StructMap(struct_map),
+            ],
+        })
+    }
+}
+
+fn md_wrap(othermdtype: &str, value: XmlDataValues) -> MdWrap {
+    MdWrap {
+        mdtype: Some("OTHER".to_string()),
+        othermdtype: Some(othermdtype.to_string()),
+        value: XmlData { value },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::TefDate;
+    use crate::identifiers::Nnt;
+    use crate::validation::validate;
+    use crate::{parse_tef, to_tef_string};
+
+    fn record() -> ThesisRecord {
+        ThesisRecord::builder()
+            .title("fr", "Étude des propriétés des matériaux")
+            .r#abstract("fr", "Résumé de la thèse")
+            .language("fr")
+            .build()
+            .unwrap()
+    }
+
+    fn admin() -> ThesisAdmin {
+        ThesisAdmin::builder()
+            .auteur(Auteur::new("Dupont", "Jean-Pierre"))
+            .nnt(&"2019LYSE1234".parse::<Nnt>().unwrap())
+            .date_accepted(TefDate::ymd(2019, 11, 5).unwrap())
+            .discipline("fr", "Physique")
+            .grantor(ThesisDegreeGrantor::new("Université de Lyon"))
+            .directeur(DirecteurThese::new("de La Fontaine", "Marie"))
+            .oai_set_spec("ddc:530")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mets = Mets::builder()
+            .agent("CREATOR", "STAR")
+            .thesis_record(record())
+            .thesis_admin(admin())
+            .build()
+            .unwrap();
+        assert_eq!(mets.objid().as_deref(), Some("2019LYSE1234"));
+
+        let parsed = parse_tef(&to_tef_string(&mets).unwrap()).unwrap();
+        let report = validate(&parsed);
+        assert_eq!(report.errors().count(), 0, "{:?}", report);
+    }
+
+    #[test]
+    fn missing_agent() {
+        let err = Mets::builder()
+            .thesis_record(record())
+            .thesis_admin(admin())
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::missing("mets/metsHdr/agent").to_string()
+        );
+    }
+}
@@ -1,10 +1,15 @@
 use crate::mets_rights::*;
-use getset::Getters;
+use crate::raw::{deserialize_elements, Others, RawElement};
+use getset::{Getters, MutGetters, Setters};
 use serde::{Deserialize, Serialize};
 
-#[cfg(feature = "gestion")]
+#[cfg(feature = "lax")]
 use lax_derive::lax;
 
+mod builder;
+
+pub use builder::{MetsBuilder, TEF_PROFILE};
+
 #[cfg(feature = "gestion")]
 use crate::gestion::{star::StarGestion, step::StepGestion};
 
@@ -12,11 +17,11 @@ use crate::gestion::{star::StarGestion, step::StepGestion};
 /// mets:agent
 /// <http://www.loc.gov/METS/>
 /// L'élément mets:agent permet de mentionner la (ou les) personne(s) ayant contribué au document METS et de préciser son (leur) rôle.
-
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Agent {
     #[serde(rename = "@ROLE")]
     role: String,
@@ -40,8 +45,10 @@ pub enum AgentValues {
 /// <http://www.loc.gov/METS/>
 /// Identifiant de notice alternatif. Cet élément permet d'assigner des identifiants alternatifs au document METS. Ces identifiants s'ajoutent à l'identifiant primaire stocké dans l'attribut OBJID de la racine mets:mets .
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct AltRecordID {
     #[serde(rename = "@ID")]
     id: String,
@@ -54,30 +61,38 @@ pub struct AltRecordID {
 /// <http://www.loc.gov/METS/>
 /// Section qui contient tous les blocs de métadonnées de gestion des entités TEF.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct AmdSec {
     #[serde(rename = "$value")]
     values: Vec<AmdSecValues>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum AmdSecValues {
     #[serde(rename = "techMD")]
     TechMD(TechMD),
     #[serde(rename = "rightsMD")]
     RightsMD(RightsMD),
-    #[serde(other)]
-    Other,
+    Other(RawElement),
 }
 
+deserialize_elements!(AmdSecValues {
+    "techMD" => TechMD,
+    "rightsMD" => RightsMD,
+});
+
 /// Division
 /// mets:div
 /// <http://www.loc.gov/METS/>
 /// Dans TEF, chaque mets:div de la carte de structure ( mets:structMap ) représente une entité du modèle TEF (la thèse, une version, une édition ou une ressource externe).
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Div {
     #[serde(rename = "@TYPE")]
     r#type: String,
@@ -89,7 +104,7 @@ pub struct Div {
     id: Option<String>,
     #[serde(rename = "@CONTENTIDS")]
     contentids: Option<String>,
-    #[serde(rename = "$value")]
+    #[serde(rename = "$value", default)]
     values: Vec<DivValues>,
 }
 
@@ -105,8 +120,10 @@ pub enum DivValues {
 /// <http://www.loc.gov/METS/>
 /// Bloc contenant les métadonnées descriptives d'une entité TEF
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct DmdSec {
     #[serde(rename = "@ID")]
     id: String,
@@ -121,8 +138,10 @@ pub struct DmdSec {
 /// <http://www.loc.gov/METS/>
 /// Fichier informatique composant une édition électronique
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct File {
     #[serde(rename = "@ID")]
     id: String,
@@ -141,8 +160,10 @@ pub struct File {
 /// <http://www.loc.gov/METS/>
 /// Cet élément permet de regrouper des fichiers ( mets:file )
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct FileGrp {
     #[serde(rename = "@USE")]
     r#use: Option<String>,
@@ -165,8 +186,10 @@ pub enum FileGrpValues {
 /// <http://www.loc.gov/METS/>
 /// Inventaire de tous les fichiers de toutes les éditions de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct FileSec {
     #[serde(rename = "@ID")]
     id: Option<String>,
@@ -179,8 +202,10 @@ pub struct FileSec {
 /// <http://www.loc.gov/METS/>
 /// Cet élément pointe vers l'emplacement d'un fichier.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct FLocat {
     #[serde(rename = "@LOCTYPE")]
     loctype: String,
@@ -195,8 +220,10 @@ pub struct FLocat {
 /// <http://www.loc.gov/METS/>
 /// L'élément mets:fptr associe un élément mets:div avec le(s) fichier(s) qui y correspond(ent).
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Fptr {
     #[serde(rename = "@FILEID")]
     fileid: Option<String>,
@@ -207,9 +234,15 @@ pub struct Fptr {
 /// <http://www.loc.gov/METS/>
 /// mets:mdWrap est un élément générique utilisé tout au long du schéma METS. Il permet de placer des métadonnées provenant de n'importe quel schéma dans un document METS. Dans TEF, ces métadonnées sont encodées en XML, via l'élément mets:xmlData .
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct MdWrap {
+    #[serde(rename = "@MDTYPE")]
+    mdtype: Option<String>,
+    #[serde(rename = "@OTHERMDTYPE")]
+    othermdtype: Option<String>,
     #[serde(rename = "$value")]
     value: XmlData,
 }
@@ -219,8 +252,10 @@ pub struct MdWrap {
 /// <http://www.loc.gov/METS/>
 /// Cet élément est la racine d'une notice TEF.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Mets {
     #[serde(rename = "@OBJID")]
     objid: Option<String>,
@@ -246,8 +281,10 @@ pub enum MetsValues {
 /// <http://www.loc.gov/METS/>
 /// Cet élément contient les métadonnées sur le document METS lui même.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct MetsHdr {
     #[serde(rename = "@CREATEDATE")]
     createdate: Option<String>,
@@ -273,7 +310,7 @@ pub enum MetsHdrValues {
 /// Nom complet de l'agent (auteur, éditeur, ...) intervenant sur le document METS.
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct Name(#[serde(rename = "$value")] String);
+pub struct Name(#[serde(rename = "$value")] pub String);
 
 /// Note sur l'agent METS
 /// mets:note
@@ -281,15 +318,17 @@ pub struct Name(#[serde(rename = "$value")] String);
 /// Toutes informations complémentaires sur les activités de l'agent (auteur, éditeur, ... ) intervenant sur la notice METS.
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct Note(#[serde(rename = "$value")] String);
+pub struct Note(#[serde(rename = "$value")] pub String);
 
 /// Bloc de métadonnées de droits
 /// mets:rightsMD
 /// <http://www.loc.gov/METS/>
 /// Bloc contenant les métadonnées de droits qui s'appliquent à une entité TEF
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct RightsMD {
     #[serde(rename = "@ID")]
     id: Option<String>,
@@ -302,8 +341,10 @@ pub struct RightsMD {
 /// <http://www.loc.gov/METS/>
 /// La carte de structure établit l'inventaire de toutes les entités TEF (la thèse, une version, une édition ou une ressource externe).
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct StructMap {
     #[serde(rename = "@TYPE")]
     r#type: String,
@@ -316,8 +357,10 @@ pub struct StructMap {
 /// <http://www.loc.gov/METS/>
 /// Un bloc mets:techMD contient soit les métadonnées administratives qui se rapportent à la thèse, soit les métadonnées de conservation qui se rapportent à chaque fichier de l'édition d'archivage.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct TechMD {
     #[serde(rename = "@ID")]
     id: String,
@@ -330,8 +373,10 @@ pub struct TechMD {
 /// <http://www.loc.gov/METS/>
 /// Élément contenant les métadonnées encodées en XML.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct XmlData {
     #[serde(rename = "$value")]
     value: XmlDataValues,
@@ -340,7 +385,7 @@ pub struct XmlData {
 use super::tef::*;
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum XmlDataValues {
     #[serde(rename = "thesisRecord")]
     ThesisRecord(ThesisRecord),
@@ -362,7 +407,103 @@ pub enum XmlDataValues {
     #[serde(rename = "step_gestion")]
     #[cfg(feature = "gestion")]
     StepGestion(StepGestion),
-    #[serde(other)]
-    #[default]
-    Other,
+    Other(RawElement),
+}
+
+impl Default for XmlDataValues {
+    fn default() -> Self {
+        XmlDataValues::Other(RawElement::default())
+    }
+}
+
+deserialize_elements!(XmlDataValues {
+    "thesisRecord" => ThesisRecord,
+    "version" => Version,
+    "edition" => Edition,
+    "ressourceExterneDescription" => RessourceExterneDescription,
+    "thesisAdmin" => ThesisAdmin,
+    "meta_fichier" => MetaFichier,
+    "RightsDeclarationMD" => RightsDeclarationMD,
+    #[cfg(feature = "gestion")]
+    "star_gestion" => StarGestion,
+    #[cfg(feature = "gestion")]
+    "step_gestion" => StepGestion,
+});
+
+impl Others for Mets {
+    fn others(&self) -> Vec<&RawElement> {
+        let mut others = vec![];
+
+        for value in self.values.iter() {
+            match value {
+                MetsValues::DmdSec(dmd_sec) => others.extend(dmd_sec.value.value.value.others()),
+                MetsValues::AmdSec(amd_sec) => {
+                    for value in amd_sec.values.iter() {
+                        match value {
+                            AmdSecValues::TechMD(md) => {
+                                others.extend(md.value.value.value.others())
+                            }
+                            AmdSecValues::RightsMD(md) => {
+                                others.extend(md.value.value.value.others())
+                            }
+                            AmdSecValues::Other(raw) => others.push(raw),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        others
+    }
+
+    fn others_mut(&mut self) -> Vec<&mut RawElement> {
+        let mut others = vec![];
+
+        for value in self.values.iter_mut() {
+            match value {
+                MetsValues::DmdSec(dmd_sec) => {
+                    others.extend(dmd_sec.value.value.value.others_mut())
+                }
+                MetsValues::AmdSec(amd_sec) => {
+                    for value in amd_sec.values.iter_mut() {
+                        match value {
+                            AmdSecValues::TechMD(md) => {
+                                others.extend(md.value.value.value.others_mut())
+                            }
+                            AmdSecValues::RightsMD(md) => {
+                                others.extend(md.value.value.value.others_mut())
+                            }
+                            AmdSecValues::Other(raw) => others.push(raw),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        others
+    }
+}
+
+impl Others for XmlDataValues {
+    fn others(&self) -> Vec<&RawElement> {
+        match self {
+            XmlDataValues::Edition(edition) => edition.others(),
+            #[cfg(feature = "gestion")]
+            XmlDataValues::ThesisAdmin(admin) => admin.others(),
+            XmlDataValues::Other(raw) => vec![raw],
+            _ => vec![],
+        }
+    }
+
+    fn others_mut(&mut self) -> Vec<&mut RawElement> {
+        match self {
+            XmlDataValues::Edition(edition) => edition.others_mut(),
+            #[cfg(feature = "gestion")]
+            XmlDataValues::ThesisAdmin(admin) => admin.others_mut(),
+            XmlDataValues::Other(raw) => vec![raw],
+            _ => vec![],
+        }
+    }
 }
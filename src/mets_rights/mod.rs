@@ -1,3 +1,4 @@
+use getset::{Getters, MutGetters, Setters};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "lax")]
@@ -9,14 +10,17 @@ use lax_derive::lax;
 /// Description d'une condition qui vient restreindre les permissions accordées
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct ConstraintDescription(#[serde(rename = "$value")] String);
+pub struct ConstraintDescription(#[serde(rename = "$value")] pub String);
 
 /// Restriction d'usage
 /// metsRights:Constraints
 /// <http://cosimo.stanford.edu/sdr/metsrights/>
 /// Condition qui vient restreindre les permissions accordées
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Constraints {
     #[serde(rename = "@CONSTRAINTTYPE")]
     constrainttype: String,
@@ -31,7 +35,10 @@ pub struct Constraints {
 /// <http://cosimo.stanford.edu/sdr/metsrights/>
 /// Autorisations et contraintes dans un contexte d'usage donné. Définition des droits par types de public.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Context {
     #[serde(rename = "@CONTEXTCLASS")]
     contextclass: String,
@@ -52,7 +59,10 @@ pub enum ContextValues {
 /// <http://cosimo.stanford.edu/sdr/metsrights/>
 /// Description des modalités d’utilisation de la thèse par le détenteur des droits dans un contexte donné
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Permissions {
     #[serde(rename = "@DISCOVER")]
     discover: Option<bool>,
@@ -80,14 +90,17 @@ pub struct Permissions {
 /// Description libre relative aux droits
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct RightsDeclaration(#[serde(rename = "$value")] String);
+pub struct RightsDeclaration(#[serde(rename = "$value")] pub String);
 
 /// Ensemble des métadonnées de droits
 /// metsRights:RightsDeclarationMD
 /// <http://cosimo.stanford.edu/sdr/metsrights/>
 /// Ensemble des métadonnées de droits
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct RightsDeclarationMD {
     #[serde(rename = "$value")]
     values: Vec<RightsDeclarationMDValues>,
@@ -106,7 +119,7 @@ pub enum RightsDeclarationMDValues {
 /// Informations sur les personnes ou institutions titulaires de droits sur une ressource externe intégrée en tout ou partie à la thèse
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct RightsHolder(#[serde(rename = "$value")] Vec<RightsHolderName>);
+pub struct RightsHolder(#[serde(rename = "$value")] pub Vec<RightsHolderName>);
 
 /// Nom du titulaire des droits sur une ressource externe
 /// metsRights:RightsHolderName
@@ -114,4 +127,4 @@ pub struct RightsHolder(#[serde(rename = "$value")] Vec<RightsHolderName>);
 /// Nom et prénom du détenteur des droits s'il s'agit d'une personne physique. Nom s'il s'agit d'une personne morale.
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
-pub struct RightsHolderName(#[serde(rename = "$value")] String);
+pub struct RightsHolderName(#[serde(rename = "$value")] pub String);
@@ -0,0 +1,126 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Particles dropped from the beginning of a family name by [`matching_key`]
+pub const PARTICLES: [&str; 18] = [
+    "d", "da", "das", "de", "del", "della", "den", "der", "des", "di", "do", "dos", "du", "ten",
+    "ter", "van", "von", "zu",
+];
+
+/// `Nom, Prénom`, or only the family name when there is no given name
+pub fn sort_name(nom: &str, prenom: &str) -> String {
+    match (nom.trim(), prenom.trim()) {
+        (nom, "") => nom.to_string(),
+        ("", prenom) => prenom.to_string(),
+        (nom, prenom) => format!("{}, {}", nom, prenom),
+    }
+}
+
+/// `Prénom Nom`
+pub fn display_name(nom: &str, prenom: &str) -> String {
+    match (nom.trim(), prenom.trim()) {
+        (nom, "") => nom.to_string(),
+        ("", prenom) => prenom.to_string(),
+        (nom, prenom) => format!("{} {}", prenom, nom),
+    }
+}
+
+/// Initials of given names, hyphens are kept: `Jean-Pierre` gives `J.-P.`, `Marie Anne` gives `M. A.`
+pub fn initials(prenom: &str) -> String {
+    prenom
+        .split_whitespace()
+        .map(|name| {
+            name.split('-')
+                .filter_map(|part| part.chars().find(|c| c.is_alphabetic()))
+                .map(|initial| format!("{}.", initial.to_uppercase()))
+                .collect::<Vec<String>>()
+                .join("-")
+        })
+        .filter(|initials| !initials.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Key to match the names of a person written in different ways.
+///
+/// Accents are removed (NFD decomposition without combining marks), case is folded, punctuation and spaces are
+/// ignored, and the leading particles of the family name are dropped: `Jean-Pierre de La Fontaine` and
+/// `Jean Pierre LAFONTAINE` share the key `lafontaine,jeanpierre`.
+pub fn matching_key(nom: &str, prenom: &str) -> String {
+    let nom = words(nom);
+    let particles = nom
+        .iter()
+        .take_while(|word| PARTICLES.contains(&word.as_str()))
+        .count();
+
+    // a family name made only of particles is kept as is
+    let nom = match particles {
+        particles if particles == nom.len() => &nom[..],
+        particles => &nom[particles..],
+    };
+
+    format!("{},{}", nom.concat(), words(prenom).concat())
+}
+
+/// Folded words of a name, ligatures are expanded
+fn words(name: &str) -> Vec<String> {
+    let folded: String = name
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'œ' => "oe".to_string(),
+            'æ' => "ae".to_string(),
+            'ß' => "ss".to_string(),
+            c if c.is_alphanumeric() => c.to_string(),
+            _ => " ".to_string(),
+        })
+        .collect();
+
+    folded.split_whitespace().map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_key() {
+        assert_eq!(
+            matching_key("de La Fontaine", "Jean-Pierre"),
+            "lafontaine,jeanpierre"
+        );
+        assert_eq!(
+            matching_key("LAFONTAINE", "Jean Pierre"),
+            matching_key("de La Fontaine", "Jean-Pierre")
+        );
+        assert_eq!(
+            matching_key("Lefèvre", "Élodie"),
+            matching_key("LEFEVRE", "elodie")
+        );
+    }
+
+    #[test]
+    fn ligatures() {
+        assert_eq!(matching_key("Lœwy", "Cæcilia"), "loewy,caecilia");
+        assert_eq!(matching_key("Straße", ""), matching_key("Strasse", ""));
+    }
+
+    #[test]
+    fn only_particles() {
+        assert_eq!(matching_key("Du", "Jean"), "du,jean");
+        assert_eq!(matching_key("Van der", "Hans"), "vander,hans");
+        assert_eq!(matching_key("Van der Berg", "Hans"), "berg,hans");
+    }
+
+    #[test]
+    fn initials_and_names() {
+        assert_eq!(initials("Jean-Pierre"), "J.-P.");
+        assert_eq!(initials("Marie Anne"), "M. A.");
+        assert_eq!(initials("élodie"), "É.");
+        assert_eq!(initials(""), "");
+        assert_eq!(sort_name("Dupont", "Jean-Pierre"), "Dupont, Jean-Pierre");
+        assert_eq!(sort_name("Dupont", " "), "Dupont");
+        assert_eq!(display_name("Dupont", "Jean-Pierre"), "Jean-Pierre Dupont");
+    }
+}
@@ -0,0 +1,420 @@
+use std::io::BufRead;
+
+use getset::Getters;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::error::{Error, Location};
+use crate::mets::Mets;
+
+/// En-tête d'un enregistrement OAI-PMH
+/// header
+/// <http://www.openarchives.org/OAI/2.0/>
+#[derive(Debug, Clone, Default, PartialEq, Getters)]
+#[getset(get = "pub")]
+pub struct Header {
+    identifier: String,
+    datestamp: String,
+    set_spec: Vec<String>,
+    deleted: bool,
+}
+
+/// Enregistrement OAI-PMH
+/// record
+/// <http://www.openarchives.org/OAI/2.0/>
+/// La notice TEF est absente lorsque l'enregistrement est supprimé.
+#[derive(Debug, Clone, Default, PartialEq, Getters)]
+#[getset(get = "pub")]
+pub struct Record {
+    header: Header,
+    metadata: Option<Mets>,
+}
+
+/// Jeton de reprise
+/// resumptionToken
+/// <http://www.openarchives.org/OAI/2.0/>
+/// Jeton permettant de demander la suite d'une liste incomplète. Un jeton vide signale la fin de la liste.
+#[derive(Debug, Clone, Default, PartialEq, Getters)]
+#[getset(get = "pub")]
+pub struct ResumptionToken {
+    token: String,
+    complete_list_size: Option<usize>,
+    cursor: Option<usize>,
+    expiration_date: Option<String>,
+}
+
+impl ResumptionToken {
+    /// Whether this token is the last one of the list
+    pub fn is_last(&self) -> bool {
+        self.token.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TextTarget {
+    Identifier,
+    Datestamp,
+    SetSpec,
+    ResumptionToken,
+    ErrorMessage,
+}
+
+/// Iterator over the records of an OAI-PMH `ListRecords` response.
+///
+/// Records are read one at a time, the whole response is never loaded in memory.
+/// The resumption token is available once the iterator is exhausted.
+pub struct ListRecords<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    resumption_token: Option<ResumptionToken>,
+    done: bool,
+}
+
+impl<R: BufRead> ListRecords<R> {
+    pub fn from_reader(reader: R) -> Self {
+        let mut reader = Reader::from_reader(reader);
+        reader.trim_text(true).check_end_names(true);
+
+        Self {
+            reader,
+            buf: Vec::new(),
+            resumption_token: None,
+            done: false,
+        }
+    }
+
+    /// Resumption token of the response, if the response has been read up to it
+    pub fn resumption_token(&self) -> Option<&ResumptionToken> {
+        self.resumption_token.as_ref()
+    }
+
+    /// Read up to the end of the next record.
+    ///
+    /// The outer error means the stream can't be read any further, the inner one only concerns the record.
+    fn next_record(&mut self) -> Result<Option<Result<Record, Error>>, Error> {
+        let mut record: Option<Record> = None;
+        let mut metadata: Option<Result<Mets, Error>> = None;
+        let mut target: Option<TextTarget> = None;
+        let mut error_code: Option<String> = None;
+        let mut error_message = String::new();
+
+        loop {
+            self.buf.clear();
+            let event = match self.reader.read_event_into(&mut self.buf) {
+                Ok(event) => event.into_owned(),
+                Err(source) => return Err(self.syntax_error(source)),
+            };
+
+            match event {
+                Event::Start(start) => match start.local_name().as_ref() {
+                    b"record" => record = Some(Record::default()),
+                    b"header" => {
+                        if let Some(record) = record.as_mut() {
+                            record.header.deleted =
+                                attribute(&start, "status")?.as_deref() == Some("deleted");
+                        }
+                    }
+                    b"identifier" if record.is_some() => target = Some(TextTarget::Identifier),
+                    b"datestamp" if record.is_some() => target = Some(TextTarget::Datestamp),
+                    b"setSpec" if record.is_some() => target = Some(TextTarget::SetSpec),
+                    b"mets" if record.is_some() => {
+                        metadata = Some(self.read_mets(start)?);
+                    }
+                    b"resumptionToken" => {
+                        self.resumption_token = Some(resumption_token(&start)?);
+                        target = Some(TextTarget::ResumptionToken);
+                    }
+                    b"error" => {
+                        error_code = attribute(&start, "code")?;
+                        target = Some(TextTarget::ErrorMessage);
+                    }
+                    _ => {}
+                },
+                Event::Empty(start) => match start.local_name().as_ref() {
+                    b"header" => {
+                        if let Some(record) = record.as_mut() {
+                            record.header.deleted =
+                                attribute(&start, "status")?.as_deref() == Some("deleted");
+                        }
+                    }
+                    b"resumptionToken" => {
+                        self.resumption_token = Some(resumption_token(&start)?);
+                    }
+                    b"error" => {
+                        if let Some(code) = attribute(&start, "code")? {
+                            if code != "noRecordsMatch" {
+                                return Err(Error::OaiPmh {
+                                    code,
+                                    message: String::new(),
+                                });
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Text(text) => {
+                    let text = text.unescape()?;
+
+                    match (target, record.as_mut()) {
+                        (Some(TextTarget::Identifier), Some(record)) => {
+                            record.header.identifier = text.into_owned()
+                        }
+                        (Some(TextTarget::Datestamp), Some(record)) => {
+                            record.header.datestamp = text.into_owned()
+                        }
+                        (Some(TextTarget::SetSpec), Some(record)) => {
+                            record.header.set_spec.push(text.into_owned())
+                        }
+                        (Some(TextTarget::ResumptionToken), _) => {
+                            if let Some(token) = self.resumption_token.as_mut() {
+                                token.token = text.into_owned();
+                            }
+                        }
+                        (Some(TextTarget::ErrorMessage), _) => error_message = text.into_owned(),
+                        _ => {}
+                    }
+                }
+                Event::End(end) => {
+                    target = None;
+
+                    match end.local_name().as_ref() {
+                        b"record" => {
+                            if let Some(mut record) = record.take() {
+                                return Ok(Some(match metadata.take().transpose() {
+                                    Ok(metadata) => {
+                                        record.metadata = metadata;
+                                        Ok(record)
+                                    }
+                                    Err(err) => Err(Error::Record {
+                                        identifier: record.header.identifier,
+                                        source: Box::new(err),
+                                    }),
+                                }));
+                            }
+                        }
+                        b"error" => {
+                            if let Some(code) = error_code.take() {
+                                // an empty list is not an error for the harvester
+                                if code != "noRecordsMatch" {
+                                    return Err(Error::OaiPmh {
+                                        code,
+                                        message: std::mem::take(&mut error_message),
+                                    });
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+
+    /// Copy the `mets:mets` element and its content, then parse it.
+    fn read_mets(&mut self, start: BytesStart<'static>) -> Result<Result<Mets, Error>, Error> {
+        let mut writer = Writer::new(Vec::new());
+        let mut depth = 1;
+
+        writer.write_event(Event::Start(start))?;
+
+        while depth > 0 {
+            self.buf.clear();
+            let event = match self.reader.read_event_into(&mut self.buf) {
+                Ok(event) => event,
+                Err(source) => return Err(self.syntax_error(source)),
+            };
+
+            match &event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                Event::Eof => {
+                    return Err(
+                        self.syntax_error(quick_xml::Error::UnexpectedEof("mets:mets".to_string()))
+                    )
+                }
+                _ => {}
+            }
+
+            writer.write_event(event)?;
+        }
+
+        Ok(crate::parse_tef_from_slice(&writer.into_inner()))
+    }
+
+    fn syntax_error(&self, source: quick_xml::Error) -> Error {
+        Error::Syntax {
+            source,
+            location: Location::new("", Some(self.reader.buffer_position())),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ListRecords<R> {
+    type Item = Result<Record, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.next_record() {
+            Ok(Some(record)) => Some(record),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+fn attribute(start: &BytesStart, name: &str) -> Result<Option<String>, Error> {
+    match start.try_get_attribute(name)? {
+        Some(attr) => Ok(Some(attr.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+fn resumption_token(start: &BytesStart) -> Result<ResumptionToken, Error> {
+    Ok(ResumptionToken {
+        token: String::new(),
+        complete_list_size: attribute(start, "completeListSize")?.and_then(|s| s.parse().ok()),
+        cursor: attribute(start, "cursor")?.and_then(|s| s.parse().ok()),
+        expiration_date: attribute(start, "expirationDate")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::THESE;
+
+    fn response(records: &str, token: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <OAI-PMH xmlns="http://www.openarchives.org/OAI/2.0/">
+              <responseDate>2019-12-02T08:00:00Z</responseDate>
+              <request verb="ListRecords" metadataPrefix="tef">https://theses.fr/oai</request>
+              <ListRecords>{records}{token}</ListRecords>
+            </OAI-PMH>"#
+        )
+    }
+
+    fn record(identifier: &str, mets: &str) -> String {
+        format!(
+            r#"<record>
+              <header>
+                <identifier>{identifier}</identifier>
+                <datestamp>2019-12-01</datestamp>
+                <setSpec>ddc:530</setSpec>
+              </header>
+              <metadata>{}</metadata>
+            </record>"#,
+            mets.trim_start_matches(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+        )
+    }
+
+    #[test]
+    fn records() {
+        let deleted = r#"<record>
+              <header status="deleted">
+                <identifier>oai:theses.fr:2018PA066001</identifier>
+                <datestamp>2019-11-30</datestamp>
+              </header>
+            </record>"#;
+        let input = response(
+            &format!("{}{deleted}", record("oai:theses.fr:2019LYSE1234", THESE)),
+            r#"<resumptionToken completeListSize="250" cursor="0">ddc:530!100</resumptionToken>"#,
+        );
+        let mut records = ListRecords::from_reader(input.as_bytes());
+
+        let these = records.next().unwrap().unwrap();
+        assert_eq!(these.header().identifier(), "oai:theses.fr:2019LYSE1234");
+        assert_eq!(these.header().datestamp(), "2019-12-01");
+        assert_eq!(these.header().set_spec(), &["ddc:530"]);
+        assert!(!these.header().deleted());
+        assert!(these.metadata().is_some());
+        assert!(records.resumption_token().is_none());
+
+        let deleted = records.next().unwrap().unwrap();
+        assert_eq!(deleted.header().identifier(), "oai:theses.fr:2018PA066001");
+        assert!(deleted.header().deleted());
+        assert!(deleted.metadata().is_none());
+
+        assert!(records.next().is_none());
+        let token = records.resumption_token().unwrap();
+        assert_eq!(token.token(), "ddc:530!100");
+        assert_eq!(token.complete_list_size(), &Some(250));
+        assert_eq!(token.cursor(), &Some(0));
+        assert!(!token.is_last());
+    }
+
+    #[test]
+    fn record_error() {
+        let invalid = THESE.replace(
+            ">Doctorat</tef:thesis.degree.level>",
+            ">Master</tef:thesis.degree.level>",
+        );
+        let input = response(
+            &format!(
+                "{}{}",
+                record("oai:theses.fr:2019LYSE0001", &invalid),
+                record("oai:theses.fr:2019LYSE1234", THESE)
+            ),
+            r#"<resumptionToken completeListSize="2" cursor="0"/>"#,
+        );
+        let mut records = ListRecords::from_reader(input.as_bytes());
+
+        match records.next() {
+            Some(Err(err @ Error::Record { .. })) => {
+                assert!(err
+                    .to_string()
+                    .starts_with("record oai:theses.fr:2019LYSE0001:"));
+                assert!(err
+                    .location()
+                    .unwrap()
+                    .path()
+                    .ends_with("thesisAdmin/thesis.degree/thesis.degree.level"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        // the stream goes on after a faulty record
+        assert!(records.next().unwrap().is_ok());
+        assert!(records.next().is_none());
+        assert!(records.resumption_token().unwrap().is_last());
+    }
+
+    #[test]
+    fn no_records_match() {
+        let input = response(
+            r#"<error code="noRecordsMatch">No matching records</error>"#,
+            "",
+        );
+
+        assert!(ListRecords::from_reader(input.as_bytes()).next().is_none());
+    }
+
+    #[test]
+    fn oai_error() {
+        let input = response(
+            r#"<error code="badResumptionToken">The token has expired</error>"#,
+            "",
+        );
+        let mut records = ListRecords::from_reader(input.as_bytes());
+
+        match records.next() {
+            Some(Err(Error::OaiPmh { code, message })) => {
+                assert_eq!(code, "badResumptionToken");
+                assert_eq!(message, "The token has expired");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(records.next().is_none());
+    }
+}
@@ -0,0 +1,304 @@
+use getset::Getters;
+use quick_xml::events::Event;
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::error::Error;
+
+/// Élément inconnu
+/// Élément que la crate ne modélise pas (mets:digiprovMD, tef:publiciteSoutenance, extension locale...), conservé
+/// tel quel pour être inspecté et resérialisé sans perte.
+///
+/// `name` est le nom qualifié tel qu'il figure dans le document, `inner_xml` le contenu brut de l'élément.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Getters)]
+#[getset(get = "pub")]
+pub struct RawElement {
+    name: String,
+    namespace: Option<String>,
+    attributes: Vec<(String, String)>,
+    inner_xml: String,
+}
+
+impl RawElement {
+    pub fn new<N: Into<String>, I: Into<String>>(
+        name: N,
+        namespace: Option<String>,
+        attributes: Vec<(String, String)>,
+        inner_xml: I,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            namespace,
+            attributes,
+            inner_xml: inner_xml.into(),
+        }
+    }
+
+    /// Element only known by its local name, until its content is read from the document
+    pub(crate) fn unknown(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    pub fn local_name(&self) -> &str {
+        self.name
+            .split_once(':')
+            .map(|(_, local)| local)
+            .unwrap_or(&self.name)
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        self.name.split_once(':').map(|(prefix, _)| prefix)
+    }
+}
+
+/// Struct name under which a [`RawElement`] is deserialized, the parser gives it the local name of the element and
+/// reads its content afterwards from the document, see [`capture`].
+pub(crate) const RAW_ELEMENT: &str = "$RawElement";
+
+/// Field holding an element the crate doesn't model, e.g. `tef:publiciteSoutenance`.
+///
+/// Only the parser reads the content of the element, deserialized directly with serde it is an unnamed element.
+impl<'de> Deserialize<'de> for RawElement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RawVisitor;
+
+        impl<'de> Visitor<'de> for RawVisitor {
+            type Value = RawElement;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "an element")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<Self::Value, E> {
+                Ok(RawElement::unknown(name.to_string()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+
+                Ok(RawElement::default())
+            }
+        }
+
+        deserializer.deserialize_struct(RAW_ELEMENT, &[], RawVisitor)
+    }
+}
+
+/// Values holding unknown elements
+pub(crate) trait Others {
+    /// Unknown elements, in document order
+    fn others(&self) -> Vec<&RawElement>;
+
+    fn others_mut(&mut self) -> Vec<&mut RawElement>;
+}
+
+/// Implement `Deserialize` for an enum of elements, unknown elements being kept in its `Other` variant.
+///
+/// `#[serde(other)]` only supports unit variants, the content of the unknown element is read afterwards from the
+/// document, see [`capture`].
+macro_rules! deserialize_elements {
+    ($type:ident { $($(#[$meta:meta])* $name:literal => $variant:ident),* $(,)? }) => {
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct ElementVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for ElementVisitor {
+                    type Value = $type;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(f, "an element of {}", stringify!($type))
+                    }
+
+                    fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                        use serde::de::VariantAccess;
+
+                        let (name, variant): (String, A::Variant) = data.variant()?;
+
+                        match name.as_str() {
+                            $(
+                                $(#[$meta])*
+                                $name => variant.newtype_variant().map($type::$variant),
+                            )*
+                            _ => {
+                                variant.newtype_variant::<serde::de::IgnoredAny>()?;
+                                Ok($type::Other($crate::raw::RawElement::unknown(name)))
+                            }
+                        }
+                    }
+                }
+
+                const VARIANTS: &[&str] = &[$($(#[$meta])* $name,)*];
+
+                deserializer.deserialize_enum(stringify!($type), VARIANTS, ElementVisitor)
+            }
+        }
+    };
+}
+
+pub(crate) use deserialize_elements;
+
+/// Read the unknown elements from the document.
+///
+/// Elements are numbered in document order, starting from the root.
+pub(crate) fn capture(input: &str, elements: &[usize]) -> Result<Vec<RawElement>, Error> {
+    let mut reader = NsReader::from_str(input);
+    let mut captured = Vec::with_capacity(elements.len());
+    let mut targets = elements.iter().copied().peekable();
+    let mut index = 0;
+
+    while targets.peek().is_some() {
+        let (namespace, event) = reader.read_resolved_event()?;
+
+        let (start, empty) = match event {
+            Event::Start(start) => (start, false),
+            Event::Empty(start) => (start, true),
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        if targets.peek() != Some(&index) {
+            index += 1;
+            continue;
+        }
+        targets.next();
+
+        let namespace = match namespace {
+            ResolveResult::Bound(namespace) => {
+                Some(String::from_utf8_lossy(namespace.as_ref()).into_owned())
+            }
+            _ => None,
+        };
+
+        let attributes = start
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute?;
+                Ok((
+                    String::from_utf8_lossy(attribute.key.as_ref()).into_owned(),
+                    attribute.unescape_value()?.into_owned(),
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+        index += 1;
+
+        let inner_xml = if empty {
+            String::new()
+        } else {
+            // the descendants are skipped but still numbered
+            let from = reader.buffer_position();
+            let mut to = from;
+            let mut depth = 1;
+
+            while depth > 0 {
+                to = reader.buffer_position();
+
+                match reader.read_event()? {
+                    Event::Start(_) => {
+                        depth += 1;
+                        index += 1;
+                    }
+                    Event::Empty(_) => index += 1,
+                    Event::End(_) => depth -= 1,
+                    Event::Eof => break,
+                    _ => {}
+                }
+            }
+
+            input[from..to].to_string()
+        };
+
+        captured.push(RawElement::new(name, namespace, attributes, inner_xml));
+    }
+
+    Ok(captured)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::these_with;
+    use crate::parse_tef;
+
+    #[test]
+    fn unknown_elements() {
+        let input = these_with(&[
+            (
+                "  </mets:amdSec>",
+                r#"    <mets:digiprovMD ID="provenance"><mets:mdWrap MDTYPE="OTHER"><mets:xmlData>STAR</mets:xmlData></mets:mdWrap></mets:digiprovMD>
+    <mets:sourceMD ID="source"/>
+  </mets:amdSec>"#,
+            ),
+            (
+                "</tef:edition>",
+                r#"<tef:tirage xmlns:ex="urn:example" ex:support="papier">3 <ex:b>exemplaires</ex:b></tef:tirage></tef:edition>"#,
+            ),
+        ]);
+        let mets = parse_tef(&input).unwrap();
+        let others = mets.others();
+        let attribute = |key: &str, value: &str| (key.to_string(), value.to_string());
+
+        assert_eq!(
+            others,
+            [
+                &RawElement::new(
+                    "tef:tirage",
+                    Some("http://www.abes.fr/abes/documents/tef".to_string()),
+                    vec![
+                        attribute("xmlns:ex", "urn:example"),
+                        attribute("ex:support", "papier")
+                    ],
+                    "3 <ex:b>exemplaires</ex:b>"
+                ),
+                &RawElement::new(
+                    "mets:digiprovMD",
+                    Some("http://www.loc.gov/METS/".to_string()),
+                    vec![attribute("ID", "provenance")],
+                    r#"<mets:mdWrap MDTYPE="OTHER"><mets:xmlData>STAR</mets:xmlData></mets:mdWrap>"#
+                ),
+                &RawElement::new(
+                    "mets:sourceMD",
+                    Some("http://www.loc.gov/METS/".to_string()),
+                    vec![attribute("ID", "source")],
+                    ""
+                ),
+            ]
+        );
+        assert_eq!(others[0].local_name(), "tirage");
+        assert_eq!(others[0].prefix(), Some("tef"));
+    }
+
+    #[cfg(feature = "gestion")]
+    #[test]
+    fn publicite_soutenance() {
+        let input = these_with(&[(
+            "</tef:thesisAdmin>",
+            r#"<tef:vie derogationDixAns="non">
+                <tef:soutenancePrevue>
+                  <tef:datePrevue>2019-10-21</tef:datePrevue>
+                  <tef:publiciteSoutenance type="publique">Soutenance publique</tef:publiciteSoutenance>
+                </tef:soutenancePrevue>
+              </tef:vie>
+            </tef:thesisAdmin>"#,
+        )]);
+        let mets = parse_tef(&input).unwrap();
+        let soutenance = mets.thesis_admin().unwrap().vie().soutenance_prevue();
+        let publicite = soutenance.publicite_soutenance().as_ref().unwrap();
+
+        assert_eq!(publicite.name(), "tef:publiciteSoutenance");
+        assert_eq!(
+            publicite.attributes(),
+            &[("type".to_string(), "publique".to_string())]
+        );
+        assert_eq!(publicite.inner_xml(), "Soutenance publique");
+        assert!(crate::to_tef_string(&mets).unwrap().contains(
+            r#"<tef:publiciteSoutenance type="publique">Soutenance publique</tef:publiciteSoutenance>"#
+        ));
+    }
+}
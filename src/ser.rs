@@ -0,0 +1,1314 @@
+use std::io::Write;
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Writer;
+
+use crate::dc::*;
+use crate::dcterms::*;
+use crate::error::Error;
+use crate::mads::*;
+use crate::mets::*;
+use crate::mets_rights::*;
+use crate::raw::{Others, RawElement};
+use crate::tef::*;
+
+#[cfg(feature = "gestion")]
+use crate::gestion::{star, step};
+
+/// Namespaces declared on the root element
+pub const NAMESPACES: [(&str, &str); 8] = [
+    ("xmlns:mets", "http://www.loc.gov/METS/"),
+    ("xmlns:tef", "http://www.abes.fr/abes/documents/tef"),
+    ("xmlns:dc", "http://purl.org/dc/elements/1.1/"),
+    ("xmlns:dcterms", "http://purl.org/dc/terms/"),
+    (
+        "xmlns:metsRights",
+        "http://cosimo.stanford.edu/sdr/metsrights/",
+    ),
+    ("xmlns:mads", "http://www.loc.gov/mads/"),
+    ("xmlns:xlink", "http://www.w3.org/1999/xlink"),
+    ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+];
+
+/// Dublin Core elements, the other children of `tef:ressourceExterneDescription` are Dublin Core terms.
+const DC_ELEMENTS: [&str; 15] = [
+    "contributor",
+    "coverage",
+    "creator",
+    "date",
+    "description",
+    "format",
+    "identifier",
+    "language",
+    "publisher",
+    "relation",
+    "rights",
+    "source",
+    "subject",
+    "title",
+    "type",
+];
+
+type Attributes<'a> = [(&'a str, Option<&'a str>)];
+
+/// Thin wrapper around [`quick_xml::Writer`], attribute values and texts are escaped.
+pub(crate) struct XmlWriter<W: Write> {
+    inner: Writer<W>,
+}
+
+impl<W: Write> XmlWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner: Writer::new_with_indent(inner, b' ', 2),
+        }
+    }
+
+    pub(crate) fn into_inner(self) -> W {
+        self.inner.into_inner()
+    }
+
+    fn event(&mut self, event: Event) -> Result<(), Error> {
+        self.inner.write_event(event).map_err(|err| match err {
+            quick_xml::Error::Io(err) => {
+                Error::Io(std::io::Error::new(err.kind(), err.to_string()))
+            }
+            err => Error::from(err),
+        })
+    }
+
+    pub(crate) fn declaration(&mut self) -> Result<(), Error> {
+        self.event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+    }
+
+    fn tag<'a>(name: &'a str, attributes: &Attributes) -> BytesStart<'a> {
+        let mut tag = BytesStart::new(name);
+
+        for (key, value) in attributes {
+            if let Some(value) = value {
+                tag.push_attribute((*key, *value));
+            }
+        }
+
+        tag
+    }
+
+    pub(crate) fn start(&mut self, name: &str, attributes: &Attributes) -> Result<(), Error> {
+        self.event(Event::Start(Self::tag(name, attributes)))
+    }
+
+    pub(crate) fn end(&mut self, name: &str) -> Result<(), Error> {
+        self.event(Event::End(BytesEnd::new(name)))
+    }
+
+    pub(crate) fn empty(&mut self, name: &str, attributes: &Attributes) -> Result<(), Error> {
+        self.event(Event::Empty(Self::tag(name, attributes)))
+    }
+
+    /// Element with only text content
+    pub(crate) fn text(
+        &mut self,
+        name: &str,
+        attributes: &Attributes,
+        text: &str,
+    ) -> Result<(), Error> {
+        if text.is_empty() {
+            return self.empty(name, attributes);
+        }
+
+        self.start(name, attributes)?;
+        self.event(Event::Text(BytesText::new(text)))?;
+        self.end(name)
+    }
+
+    /// Element with children
+    pub(crate) fn element<F>(
+        &mut self,
+        name: &str,
+        attributes: &Attributes,
+        children: F,
+    ) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error>,
+    {
+        self.start(name, attributes)?;
+        children(self)?;
+        self.end(name)
+    }
+
+    pub(crate) fn all<T: ToXml>(&mut self, values: &[T]) -> Result<(), Error> {
+        values.iter().try_for_each(|value| value.to_xml(self))
+    }
+
+    pub(crate) fn optional<T: ToXml>(&mut self, value: &Option<T>) -> Result<(), Error> {
+        match value {
+            Some(value) => value.to_xml(self),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn optional_all<T: ToXml>(&mut self, values: &Option<Vec<T>>) -> Result<(), Error> {
+        match values {
+            Some(values) => self.all(values),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Write an element of the model as namespaced TEF
+pub(crate) trait ToXml {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error>;
+}
+
+impl<T: ToXml> ToXml for Box<T> {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        self.as_ref().to_xml(w)
+    }
+}
+
+/// Serialize a TEF record to a namespaced XML document
+pub fn to_tef_string(mets: &Mets) -> Result<String, Error> {
+    let mut buffer = Vec::new();
+    write_tef(mets, &mut buffer)?;
+
+    String::from_utf8(buffer).map_err(|e| Error::InvalidEncoding {
+        encoding: "UTF-8",
+        offset: e.utf8_error().valid_up_to(),
+    })
+}
+
+/// Write a TEF record as a namespaced XML document
+pub fn write_tef<W: Write>(mets: &Mets, writer: W) -> Result<(), Error> {
+    let mut w = XmlWriter::new(writer);
+    w.declaration()?;
+    mets.to_xml(&mut w)?;
+    w.into_inner().flush()?;
+
+    Ok(())
+}
+
+// mets
+
+impl ToXml for Mets {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let mut attributes: Vec<(&str, Option<&str>)> = NAMESPACES
+            .iter()
+            .map(|(key, value)| (*key, Some(*value)))
+            .collect();
+        let declarations = declarations(self);
+        attributes.extend(
+            declarations
+                .iter()
+                .map(|(key, value)| (key.as_str(), Some(value.as_str()))),
+        );
+        attributes.push(("OBJID", self.objid().as_deref()));
+        attributes.push(("PROFILE", self.profile().as_deref()));
+
+        w.element("mets:mets", &attributes, |w| w.all(self.values()))
+    }
+}
+
+/// Namespaces of the unknown elements that are not declared by [`NAMESPACES`] nor by the elements themselves
+fn declarations(mets: &Mets) -> Vec<(String, String)> {
+    let mut declarations: Vec<(String, String)> = vec![];
+
+    for raw in mets.others() {
+        let Some(namespace) = raw.namespace() else {
+            continue;
+        };
+        let key = match raw.prefix() {
+            Some(prefix) => format!("xmlns:{prefix}"),
+            None => "xmlns".to_string(),
+        };
+
+        let declared = NAMESPACES.iter().any(|(k, _)| *k == key)
+            || raw.attributes().iter().any(|(k, _)| *k == key)
+            || declarations.iter().any(|(k, _)| *k == key);
+
+        if !declared {
+            declarations.push((key, namespace.clone()));
+        }
+    }
+
+    declarations
+}
+
+impl ToXml for MetsValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            MetsValues::MetsHdr(value) => value.to_xml(w),
+            MetsValues::DmdSec(value) => value.to_xml(w),
+            MetsValues::AmdSec(value) => value.to_xml(w),
+            MetsValues::FileSec(value) => value.to_xml(w),
+            MetsValues::StructMap(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for MetsHdr {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("CREATEDATE", self.createdate().as_deref()),
+            ("LASTMODDATE", self.lastmoddate().as_deref()),
+            ("RECORDSTATUS", self.recordstatus().as_deref()),
+        ];
+
+        w.element("mets:metsHdr", &attributes, |w| w.all(self.values()))
+    }
+}
+
+impl ToXml for MetsHdrValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            MetsHdrValues::Agent(value) => value.to_xml(w),
+            MetsHdrValues::AltRecordID(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for Agent {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("ROLE", Some(self.role().as_str())),
+            ("OTHERROLE", self.otherrole().as_deref()),
+        ];
+
+        w.element("mets:agent", &attributes, |w| w.all(self.values()))
+    }
+}
+
+impl ToXml for AgentValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            AgentValues::Name(value) => w.text("mets:name", &[], &value.0),
+            AgentValues::Note(value) => w.text("mets:note", &[], &value.0),
+        }
+    }
+}
+
+impl ToXml for AltRecordID {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("ID", Some(self.id().as_str())),
+            ("TYPE", self.r#type().as_deref()),
+        ];
+
+        w.empty("mets:altRecordID", &attributes)
+    }
+}
+
+impl ToXml for DmdSec {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("ID", Some(self.id().as_str())),
+            ("CREATED", self.created().as_deref()),
+        ];
+
+        w.element("mets:dmdSec", &attributes, |w| self.value().to_xml(w))
+    }
+}
+
+impl ToXml for AmdSec {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("mets:amdSec", &[], |w| w.all(self.values()))
+    }
+}
+
+impl ToXml for AmdSecValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            AmdSecValues::TechMD(value) => value.to_xml(w),
+            AmdSecValues::RightsMD(value) => value.to_xml(w),
+            AmdSecValues::Other(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for TechMD {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("mets:techMD", &[("ID", Some(self.id().as_str()))], |w| {
+            self.value().to_xml(w)
+        })
+    }
+}
+
+impl ToXml for RightsMD {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("mets:rightsMD", &[("ID", self.id().as_deref())], |w| {
+            self.value().to_xml(w)
+        })
+    }
+}
+
+impl ToXml for MdWrap {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("MDTYPE", self.mdtype().as_deref()),
+            ("OTHERMDTYPE", self.othermdtype().as_deref()),
+        ];
+
+        w.element("mets:mdWrap", &attributes, |w| {
+            w.element("mets:xmlData", &[], |w| self.value().value().to_xml(w))
+        })
+    }
+}
+
+impl ToXml for XmlDataValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            XmlDataValues::ThesisRecord(value) => value.to_xml(w),
+            XmlDataValues::Version(value) => value.to_xml(w),
+            XmlDataValues::Edition(value) => value.to_xml(w),
+            XmlDataValues::RessourceExterneDescription(value) => value.to_xml(w),
+            XmlDataValues::ThesisAdmin(value) => value.to_xml(w),
+            XmlDataValues::MetaFichier(value) => value.to_xml(w),
+            XmlDataValues::RightsDeclarationMD(value) => value.to_xml(w),
+            #[cfg(feature = "gestion")]
+            XmlDataValues::StarGestion(value) => value.to_xml(w),
+            #[cfg(feature = "gestion")]
+            XmlDataValues::StepGestion(value) => value.to_xml(w),
+            XmlDataValues::Other(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for FileSec {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("mets:fileSec", &[("ID", self.id().as_deref())], |w| {
+            w.all(self.value())
+        })
+    }
+}
+
+impl ToXml for FileGrp {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("ID", self.id().as_deref()),
+            ("USE", self.r#use().as_deref()),
+        ];
+
+        w.element("mets:fileGrp", &attributes, |w| w.all(self.values()))
+    }
+}
+
+impl ToXml for FileGrpValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            FileGrpValues::File(value) => value.to_xml(w),
+            FileGrpValues::FileGrp(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for File {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("ID", Some(self.id().as_str())),
+            ("MIMETYPE", self.mimetype().as_deref()),
+            ("ADMID", Some(self.admid().as_str())),
+            ("USE", self.r#use().as_deref()),
+        ];
+
+        w.element("mets:file", &attributes, |w| self.value().to_xml(w))
+    }
+}
+
+impl ToXml for FLocat {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("LOCTYPE", Some(self.loctype().as_str())),
+            ("OTHERLOCTYPE", self.otherloctype().as_deref()),
+            ("xlink:href", Some(self.href().as_str())),
+        ];
+
+        w.empty("mets:FLocat", &attributes)
+    }
+}
+
+impl ToXml for StructMap {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element(
+            "mets:structMap",
+            &[("TYPE", Some(self.r#type().as_str()))],
+            |w| w.all(self.value()),
+        )
+    }
+}
+
+impl ToXml for Div {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("ID", self.id().as_deref()),
+            ("TYPE", Some(self.r#type().as_str())),
+            ("DMDID", self.dmdid().as_deref()),
+            ("ADMID", self.amdid().as_deref()),
+            ("CONTENTIDS", self.contentids().as_deref()),
+        ];
+
+        if self.values().is_empty() {
+            return w.empty("mets:div", &attributes);
+        }
+
+        w.element("mets:div", &attributes, |w| w.all(self.values()))
+    }
+}
+
+impl ToXml for DivValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            DivValues::Div(value) => value.to_xml(w),
+            DivValues::Fptr(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for Fptr {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.empty("mets:fptr", &[("FILEID", self.fileid().as_deref())])
+    }
+}
+
+// metsRights
+
+impl ToXml for RightsDeclarationMD {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("metsRights:RightsDeclarationMD", &[], |w| {
+            w.all(self.values())
+        })
+    }
+}
+
+impl ToXml for RightsDeclarationMDValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            RightsDeclarationMDValues::RightsDeclaration(value) => {
+                w.text("metsRights:RightsDeclaration", &[], &value.0)
+            }
+            RightsDeclarationMDValues::Context(value) => value.to_xml(w),
+            RightsDeclarationMDValues::RightsHolder(value) => {
+                w.element("metsRights:RightsHolder", &[], |w| {
+                    value
+                        .0
+                        .iter()
+                        .try_for_each(|name| w.text("metsRights:RightsHolderName", &[], &name.0))
+                })
+            }
+        }
+    }
+}
+
+impl ToXml for Context {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("CONTEXTCLASS", Some(self.contextclass().as_str())),
+            ("OTHERCONTEXTTYPE", self.othercontexttype().as_deref()),
+        ];
+
+        w.element("metsRights:Context", &attributes, |w| w.all(self.values()))
+    }
+}
+
+impl ToXml for ContextValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            ContextValues::Permissions(value) => value.to_xml(w),
+            ContextValues::Constraints(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for Permissions {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let flag = |value: &Option<bool>| value.map(|value| if value { "true" } else { "false" });
+
+        let attributes = [
+            ("DISCOVER", flag(self.discover())),
+            ("DISPLAY", flag(self.display())),
+            ("COPY", flag(self.copy())),
+            ("DUPLICATE", flag(self.duplicate())),
+            ("MODIFY", flag(self.modify())),
+            ("DELETE", flag(self.delete())),
+            ("PRINT", flag(self.print())),
+            ("OTHER", flag(self.other())),
+            ("OTHERPERMITTYPE", self.otherpermittype().as_deref()),
+        ];
+
+        w.empty("metsRights:Permissions", &attributes)
+    }
+}
+
+impl ToXml for Constraints {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("CONSTRAINTTYPE", Some(self.constrainttype().as_str())),
+            ("OTHERCONSTRAINTTYPE", self.otherconstrainttype().as_deref()),
+        ];
+
+        w.element("metsRights:Constraints", &attributes, |w| {
+            self.values()
+                .iter()
+                .try_for_each(|value| w.text("metsRights:ConstraintDescription", &[], &value.0))
+        })
+    }
+}
+
+// dc & dcterms
+
+impl ToXml for Coverage {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text("dc:coverage", &[], &self.0)
+    }
+}
+
+impl ToXml for Identifier {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dc:identifier",
+            &[("xsi:type", Some(self.r#type()))],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for Language {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dc:language",
+            &[("xsi:type", Some(self.r#type()))],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for Subject {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("xml:lang", self.lang().as_deref()),
+            ("xsi:type", self.r#type().as_deref()),
+        ];
+
+        w.text("dc:subject", &attributes, self.text())
+    }
+}
+
+impl ToXml for Title {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("xml:lang", Some(self.lang().as_str())),
+            ("xsi:type", self.r#type().as_deref()),
+        ];
+
+        w.text("dc:title", &attributes, self.text())
+    }
+}
+
+impl ToXml for Type {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dc:type",
+            &[("xsi:type", self.r#type().as_deref())],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for Abstract {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dcterms:abstract",
+            &[("xml:lang", Some(self.lang()))],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for Alternative {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dcterms:alternative",
+            &[("xml:lang", Some(self.lang()))],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for DateAccepted {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dcterms:dateAccepted",
+            &[("xsi:type", Some(self.r#type()))],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for Extent {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text("dcterms:extent", &[], &self.0)
+    }
+}
+
+impl ToXml for Issued {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dcterms:issued",
+            &[("xsi:type", Some(self.r#type()))],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for Medium {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dcterms:medium",
+            &[("xsi:type", Some(self.r#type()))],
+            self.text(),
+        )
+    }
+}
+
+impl ToXml for Replaces {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "dcterms:replaces",
+            &[("xsi:type", Some(self.r#type()))],
+            self.text(),
+        )
+    }
+}
+
+// mads
+
+impl ToXml for NamePart {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text(
+            "mads:namePart",
+            &[("type", Some(self.r#type()))],
+            self.value(),
+        )
+    }
+}
+
+impl ToXml for Description {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text("mads:description", &[], self.value())
+    }
+}
+
+// tef
+
+/// Newtypes holding the text of a `tef:` element
+macro_rules! tef_text {
+    ($($type:ty => $name:literal),* $(,)?) => {
+        $(
+            impl ToXml for $type {
+                fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+                    w.text($name, &[], &self.0)
+                }
+            }
+        )*
+    };
+}
+
+tef_text!(
+    AutoriteInterne => "tef:autoriteInterne",
+    AvisJury => "tef:avisJury",
+    DateNaissance => "tef:dateNaissance",
+    Nom => "tef:nom",
+    NomDeNaissance => "tef:nomDeNaissance",
+    NoteFichier => "tef:noteFichier",
+    NoteVersion => "tef:noteVersion",
+    OaiSetSpec => "tef:oaiSetSpec",
+    Place => "tef:place",
+    Prenom => "tef:prenom",
+    RessourceID => "tef:ressourceID",
+    StructureFichier => "tef:structureFichier",
+    Taille => "tef:taille",
+    TheseSurTravaux => "tef:theseSurTravaux",
+    ThesisDegreeName => "tef:thesis.degree.name",
+);
+
+impl ToXml for AutoriteExterne {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [("autoriteSource", Some(self.autorite_source().as_str()))];
+
+        w.text("tef:autoriteExterne", &attributes, self.text())
+    }
+}
+
+impl ToXml for Auteur {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:auteur", &[], |w| {
+            self.nom().to_xml(w)?;
+            self.prenom().to_xml(w)?;
+            w.optional(self.nom_de_naissance())?;
+            w.optional(self.date_naissance())?;
+
+            if let Some(nationalite) = self.nationalite() {
+                w.text(
+                    "tef:nationalite",
+                    &[("scheme", Some("ISO-3166-1"))],
+                    nationalite,
+                )?;
+            }
+
+            w.optional_all(self.autorite_externe())
+        })
+    }
+}
+
+/// Persons and organizations with a name and authority identifiers
+macro_rules! tef_agent {
+    ($type:ty => $name:literal, $($field:ident),*) => {
+        impl ToXml for $type {
+            fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+                w.element($name, &[], |w| {
+                    $(self.$field().to_xml(w)?;)*
+                    w.optional_all(self.autorite_interne())?;
+                    w.optional_all(self.autorite_externe())
+                })
+            }
+        }
+    };
+}
+
+tef_agent!(DirecteurThese => "tef:directeurThese", nom, prenom);
+tef_agent!(PresidentJury => "tef:presidentJury", nom, prenom);
+tef_agent!(MembreJury => "tef:membreJury", nom, prenom);
+tef_agent!(Rapporteur => "tef:rapporteur", nom, prenom);
+tef_agent!(EcoleDoctorale => "tef:ecoleDoctorale", nom);
+tef_agent!(ThesisDegreeGrantor => "tef:thesis.degree.grantor", nom);
+tef_agent!(Editeur => "tef:editeur", nom, place);
+
+impl ToXml for PartenaireRecherche {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let r#type = match self.r#type() {
+            PartenaireRechercheValues::Laboratoire => "laboratoire",
+            PartenaireRechercheValues::Entreprise => "entreprise",
+            PartenaireRechercheValues::Fondation => "fondation",
+            PartenaireRechercheValues::EquipeRecherche => "equipeRecherche",
+            PartenaireRechercheValues::AutreType => "autreType",
+        };
+
+        let attributes = [
+            ("type", Some(r#type)),
+            ("autreType", self.autre_type().as_deref()),
+        ];
+
+        w.element("tef:partenaireRecherche", &attributes, |w| {
+            self.nom().to_xml(w)?;
+            w.optional_all(self.autorite_interne())?;
+            w.optional_all(self.autorite_externe())
+        })
+    }
+}
+
+impl ToXml for MADSAuthority {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("authorityID", Some(self.authority_id().as_str())),
+            ("type", Some(self.r#type().as_str())),
+        ];
+
+        w.element("tef:MADSAuthority", &attributes, |w| self.value().to_xml(w))
+    }
+}
+
+impl ToXml for PersonMADS {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:personMADS", &[], |w| {
+            w.all(self.name_part())?;
+            w.optional(self.description())
+        })
+    }
+}
+
+impl ToXml for ThesisRecord {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:thesisRecord", &[], |w| {
+            self.title().to_xml(w)?;
+            w.optional_all(self.alternative())?;
+            w.optional_all(self.subject())?;
+            w.optional(self.sujet_rameau())?;
+            w.optional_all(self.r#abstract())?;
+            w.all(self.r#type())?;
+            w.all(self.language())?;
+            w.optional_all(self.coverage())
+        })
+    }
+}
+
+impl ToXml for SujetRameau {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:sujetRameau", &[], |w| {
+            self.values()
+                .iter()
+                .flatten()
+                .try_for_each(|value| value.to_xml(w))
+        })
+    }
+}
+
+impl ToXml for SujetRameauValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            SujetRameauValues::VedetteRameauPersonne(value) => value.to_xml(w),
+            SujetRameauValues::VedetteRameauCollectivite(value) => value.to_xml(w),
+            SujetRameauValues::VedetteRameauFamille(value) => value.to_xml(w),
+            SujetRameauValues::VedetteRameauAuteurTitre(value) => value.to_xml(w),
+            SujetRameauValues::VedetteRameauTitre(value) => value.to_xml(w),
+            SujetRameauValues::VedetteRameauNomCommun(value) => value.to_xml(w),
+            SujetRameauValues::VedetteRameauNomGeographique(value) => value.to_xml(w),
+            SujetRameauValues::VedetteRameauGenreForme(value) => value.to_xml(w),
+        }
+    }
+}
+
+macro_rules! tef_vedette {
+    ($($type:ty => $name:literal),* $(,)?) => {
+        $(
+            impl ToXml for $type {
+                fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+                    w.element($name, &[], |w| {
+                        self.element_entree().to_xml(w)?;
+                        w.optional_all(self.subdivision())
+                    })
+                }
+            }
+        )*
+    };
+}
+
+tef_vedette!(
+    VedetteRameauPersonne => "tef:vedetteRameauPersonne",
+    VedetteRameauCollectivite => "tef:vedetteRameauCollectivite",
+    VedetteRameauFamille => "tef:vedetteRameauFamille",
+    VedetteRameauAuteurTitre => "tef:vedetteRameauAuteurTitre",
+    VedetteRameauTitre => "tef:vedetteRameauTitre",
+    VedetteRameauNomCommun => "tef:vedetteRameauNomCommun",
+    VedetteRameauNomGeographique => "tef:vedetteRameauNomGeographique",
+    VedetteRameauGenreForme => "tef:vedetteRameauGenreForme",
+);
+
+impl ToXml for ElementdEntree {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("autoriteExterne", self.autorite_externe().as_deref()),
+            ("autoriteSource", self.autorite_source().as_deref()),
+        ];
+
+        w.text("tef:elementdEntree", &attributes, self.text())
+    }
+}
+
+impl ToXml for Subdivision {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("type", Some(self.r#type().as_str())),
+            ("autoriteExterne", self.autorite_externe().as_deref()),
+            ("autoriteSource", self.autorite_source().as_deref()),
+        ];
+
+        w.text("tef:subdivision", &attributes, self.text())
+    }
+}
+
+impl ToXml for ThesisAdmin {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:thesisAdmin", &[], |w| {
+            self.auteur().to_xml(w)?;
+            w.all(self.identifier())?;
+            self.date_accepted().to_xml(w)?;
+            self.thesis_degree().to_xml(w)?;
+            self.these_sur_travaux().to_xml(w)?;
+            self.avis_jury().to_xml(w)?;
+            w.all(self.directeur_these())?;
+            w.optional(self.president_jury())?;
+            w.all(self.membre_jury())?;
+            w.all(self.rapporteur())?;
+            w.all(self.ecole_doctorale())?;
+            w.all(self.partenaire_recherche())?;
+            w.all(self.oai_set_spec())?;
+            w.optional_all(self.mads_authority())?;
+            #[cfg(feature = "gestion")]
+            self.vie().to_xml(w)?;
+
+            Ok(())
+        })
+    }
+}
+
+impl ToXml for ThesisDegree {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:thesis.degree", &[], |w| {
+            self.discipline().to_xml(w)?;
+            w.all(self.grantor())?;
+            self.level().to_xml(w)?;
+            w.optional(self.name())?;
+            #[cfg(feature = "gestion")]
+            {
+                w.optional(self.date_premiere_inscription_doctorat())?;
+                w.optional(self.date_inscription_etab())?;
+                if let Some(contrat) = self.contrat_doctoral() {
+                    w.text("tef:contratDoctoral", &[], contrat)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl ToXml for ThesisDegreeDiscipline {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [("xml:lang", Some(self.lang().as_str()))];
+
+        w.text("tef:thesis.degree.discipline", &attributes, self.text())
+    }
+}
+
+impl ToXml for ThesisDegreeLevel {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.text("tef:thesis.degree.level", &[], self.text().as_str())
+    }
+}
+
+impl ToXml for Version {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:version", &[], |w| {
+            w.all(self.manque())?;
+            w.all(self.replaces())
+        })
+    }
+}
+
+impl ToXml for Manque {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:manque", &[], |w| {
+            w.optional(self.ressource_id())?;
+            w.optional(self.note_version())
+        })
+    }
+}
+
+impl ToXml for Edition {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:edition", &[], |w| w.all(self.values()))
+    }
+}
+
+impl ToXml for EditionValues {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        match self {
+            EditionValues::Medium(value) => value.to_xml(w),
+            EditionValues::Extent(value) => value.to_xml(w),
+            EditionValues::Identifier(value) => value.to_xml(w),
+            EditionValues::Issued(value) => value.to_xml(w),
+            EditionValues::Replaces(value) => value.to_xml(w),
+            EditionValues::Editeur(value) => value.to_xml(w),
+            EditionValues::Other(value) => value.to_xml(w),
+        }
+    }
+}
+
+impl ToXml for RessourceExterneDescription {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let mut values: Vec<(&String, &String)> = self.0.iter().collect();
+        values.sort();
+
+        w.element("tef:ressourceExterneDescription", &[], |w| {
+            values.into_iter().try_for_each(|(name, value)| {
+                let prefix = if DC_ELEMENTS.contains(&name.as_str()) {
+                    "dc"
+                } else {
+                    "dcterms"
+                };
+
+                w.text(&format!("{}:{}", prefix, name), &[], value)
+            })
+        })
+    }
+}
+
+impl ToXml for MetaFichier {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("tef:meta_fichier", &[], |w| {
+            w.optional(self.encodage())?;
+            self.format_fichier().to_xml(w)?;
+            w.optional(self.note_fichier())?;
+            w.optional(self.structure_fichier())?;
+            self.taille().to_xml(w)
+        })
+    }
+}
+
+impl ToXml for Encodage {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let encodage = match self.text() {
+            EncodageValues::ASCII => "ASCII",
+            EncodageValues::Latin1 => "Latin 1",
+            EncodageValues::Unicode => "Unicode",
+        };
+
+        w.text("tef:encodage", &[], encodage)
+    }
+}
+
+impl ToXml for FormatFichier {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let format = match self.text() {
+            FormatFichierValues::OpenDocument => "OpenDocument",
+            FormatFichierValues::PDF => "PDF",
+            FormatFichierValues::PDFA => "PDF/A",
+            FormatFichierValues::HTML => "HTML",
+            FormatFichierValues::RTF => "RTF",
+            FormatFichierValues::TXT => "TXT",
+            FormatFichierValues::XML => "XML",
+            FormatFichierValues::JPEG => "JPEG",
+            FormatFichierValues::GIF => "GIF",
+            FormatFichierValues::PNG => "PNG",
+            FormatFichierValues::TIFF => "TIFF",
+            FormatFichierValues::MP3 => "MP3",
+            FormatFichierValues::MPEG => "MPEG",
+            FormatFichierValues::QuickTime => "QuickTime",
+            FormatFichierValues::AutreFormat => "autreFormat",
+        };
+
+        w.text("tef:formatFichier", &[], format)
+    }
+}
+
+// gestion
+
+#[cfg(feature = "gestion")]
+impl ToXml for DatePremiereInscriptionDoctorat {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [("type", self.r#type().as_deref())];
+
+        w.text(
+            "tef:datePremiereInscriptionDoctorat",
+            &attributes,
+            self.text(),
+        )
+    }
+}
+
+#[cfg(feature = "gestion")]
+impl ToXml for DateInscriptionEtab {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [("type", self.r#type().as_deref())];
+
+        w.text("tef:dateInscriptionEtab", &attributes, self.text())
+    }
+}
+
+#[cfg(feature = "gestion")]
+impl ToXml for Vie {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("derogationDixAns", Some(self.derogation_dix_ans().as_str())),
+            ("commentDixAns", self.comment_dix_ans().as_deref()),
+        ];
+
+        w.element("tef:vie", &attributes, |w| {
+            let soutenance = self.soutenance_prevue();
+
+            w.element("tef:soutenancePrevue", &[], |w| {
+                let fields = [
+                    ("tef:datePrevue", soutenance.date_prevue()),
+                    ("tef:heurePrevue", soutenance.heure_prevue()),
+                    ("tef:lieuPrevue", soutenance.lieu_prevue()),
+                ];
+
+                fields.iter().try_for_each(|(name, value)| match value {
+                    Some(value) => w.text(name, &[], value),
+                    None => Ok(()),
+                })?;
+                w.optional(soutenance.publicite_soutenance())
+            })?;
+
+            match self.date_abandon() {
+                Some(date) => w.text("tef:dateAbandon", &[], date),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+#[cfg(feature = "gestion")]
+impl ToXml for star::StarGestion {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("codeEtab", Some(self.code_etab().as_str())),
+            ("libEtab", Some(self.lib_etab().as_str())),
+            ("ppnEtab", Some(self.ppn_etab().as_str())),
+            ("enProd", Some(self.en_prod().as_str())),
+            ("ID_THESE", Some(self.id_these().as_str())),
+            ("scenarioEtab", Some(self.scenario_etab().as_str())),
+            ("etat", Some(self.etat().as_str())),
+        ];
+
+        w.element("star_gestion", &attributes, |w| {
+            let traitements = self.traitements();
+
+            w.element(
+                "traitements",
+                &[("scenario", Some(traitements.scenario()))],
+                |w| {
+                    w.empty("entree", &[])?;
+                    traitements
+                        .step()
+                        .iter()
+                        .try_for_each(|_| w.empty("step", &[]))?;
+                    w.empty("maj", &[])?;
+                    w.empty("facile", &[])?;
+                    w.empty("ctrUrl", &[])?;
+                    w.empty("remonteeArchive", &[])?;
+                    w.empty("purge", &[])?;
+                    w.empty("invalidation", &[])?;
+                    traitements.sorties().to_xml(w)
+                },
+            )?;
+
+            w.empty("workflow", &[])
+        })
+    }
+}
+
+#[cfg(feature = "gestion")]
+impl ToXml for star::Sorties {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("sorties", &[("date", Some(self.date().as_str()))], |w| {
+            let cines = self.cines();
+            let attributes = [
+                ("numeroPAC", Some(cines.numero_pac().as_str())),
+                ("dateCines", Some(cines.date_cines().as_str())),
+                ("indicCines", Some(cines.indic_cines().as_str())),
+                ("trace", Some(cines.trace().as_str())),
+            ];
+            w.empty("cines", &attributes)?;
+            w.empty("sudoc", &[])?;
+
+            let diffusion = self.diffusion();
+            let attributes = [
+                ("urlPerenne", Some(diffusion.url_perenne().as_str())),
+                (
+                    "conformitePolDiffusion",
+                    Some(diffusion.conformite_pol_diffusion().as_str()),
+                ),
+                ("typeDiffusion", Some(diffusion.type_diffusion().as_str())),
+                (
+                    "restrictionTemporelleType",
+                    Some(diffusion.restriction_temporelle_type().as_str()),
+                ),
+                (
+                    "restrictionTemporelleFin",
+                    Some(diffusion.restriction_temporelle_fin().as_str()),
+                ),
+                ("embargoFin", Some(diffusion.embargo_fin().as_str())),
+                (
+                    "confidentialiteFin",
+                    Some(diffusion.confidentialite_fin().as_str()),
+                ),
+            ];
+            w.empty("diffusion", &attributes)
+        })
+    }
+}
+
+#[cfg(feature = "gestion")]
+impl ToXml for step::StepGestion {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let attributes = [
+            ("codeEtab", Some(self.code_etab().as_str())),
+            ("libEtab", Some(self.lib_etab().as_str())),
+            ("ppnEtab", Some(self.ppn_etab().as_str())),
+            ("enProdStep", Some(self.en_prod_step().as_str())),
+            ("ID_SUJET", Some(self.id_sujet().as_str())),
+            ("stepEtat", Some(self.step_etat().as_str())),
+        ];
+
+        w.element("step_gestion", &attributes, |w| {
+            w.element("traitements", &[], |w| {
+                w.empty("entree", &[])?;
+                w.empty("maj", &[])?;
+                self.traitements().sorties().to_xml(w)
+            })?;
+
+            w.empty("workflow", &[])
+        })
+    }
+}
+
+#[cfg(feature = "gestion")]
+impl ToXml for step::Sorties {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.element("sorties", &[], |w| {
+            let nnt = self.nnt();
+            let attributes = [
+                ("dateNnt", nnt.date_nnt().as_deref()),
+                ("sourceNnt", nnt.source_nnt().as_deref()),
+                ("indicNnt", nnt.indic_nnt().as_deref()),
+                ("trace", nnt.trace().as_deref()),
+            ];
+            w.text("nnt", &attributes, nnt.text())?;
+            w.empty("star", &[])?;
+            w.empty("sudoc", &[])?;
+            w.empty("diffusion", &[])
+        })
+    }
+}
+
+// unknown elements
+
+impl ToXml for RawElement {
+    fn to_xml<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        // placeholder of a missing element
+        if self.name().is_empty() {
+            return Ok(());
+        }
+
+        let mut tag = BytesStart::new(self.name().as_str());
+        tag.extend_attributes(
+            self.attributes()
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
+        if self.inner_xml().is_empty() {
+            return w.event(Event::Empty(tag));
+        }
+
+        w.event(Event::Start(tag))?;
+        w.event(Event::Text(BytesText::from_escaped(
+            self.inner_xml().as_str(),
+        )))?;
+        w.end(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::THESE;
+
+    #[test]
+    fn round_trip() {
+        let mets = parse_tef(THESE).unwrap();
+
+        assert_eq!(parse_tef(&to_tef_string(&mets).unwrap()).unwrap(), mets);
+    }
+
+    #[test]
+    fn md_wrap_without_mdtype() {
+        let input = THESE.replace(r#" MDTYPE="OTHER" OTHERMDTYPE="dr_these""#, "");
+        let mets = parse_tef(&input).unwrap();
+        let output = to_tef_string(&mets).unwrap();
+
+        assert!(!output.contains("dr_these"));
+        assert_eq!(output.matches(" MDTYPE=").count(), 4);
+        assert_eq!(parse_tef(&output).unwrap(), mets);
+    }
+}
@@ -0,0 +1,311 @@
+use std::fmt::Display;
+
+use super::*;
+use crate::identifiers::Ppn;
+use crate::names;
+
+/// Element linked to authority records, with `tef:autoriteInterne` and `tef:autoriteExterne`
+pub trait Authorities {
+    fn autorites_internes(&self) -> &[AutoriteInterne];
+
+    fn autorites_externes(&self) -> &[AutoriteExterne];
+
+    /// PPN of the first Sudoc or IdRef authority record
+    fn ppn(&self) -> Option<Ppn> {
+        self.autorites_externes()
+            .iter()
+            .find_map(AutoriteExterne::ppn)
+    }
+
+    /// First `tef:MADSAuthority` of the thesis admin data this element points to
+    fn resolve_authority<'a>(&self, admin: &'a ThesisAdmin) -> Option<&'a MADSAuthority> {
+        self.autorites_internes()
+            .iter()
+            .find_map(|autorite| admin.authority(&autorite.0))
+    }
+}
+
+/// Person taking part in the thesis: author, director, jury member...
+pub trait Person: Authorities {
+    fn nom(&self) -> &Nom;
+
+    fn prenom(&self) -> &Prenom;
+
+    /// `Nom, Prénom`
+    fn sort_name(&self) -> String {
+        names::sort_name(&self.nom().0, &self.prenom().0)
+    }
+
+    /// `Prénom Nom`
+    fn display_name(&self) -> String {
+        names::display_name(&self.nom().0, &self.prenom().0)
+    }
+
+    /// Initials of the given names, e.g. `J.-P.`
+    fn initials(&self) -> String {
+        names::initials(&self.prenom().0)
+    }
+
+    /// Key to match the person across records, see [`names::matching_key`]
+    fn matching_key(&self) -> String {
+        names::matching_key(&self.nom().0, &self.prenom().0)
+    }
+}
+
+/// Organization taking part in the thesis: grantor, doctoral school, research partner, publisher
+pub trait Organization: Authorities {
+    fn nom(&self) -> &Nom;
+}
+
+/// Role of a person in the thesis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    /// tef:auteur
+    Author,
+    /// tef:directeurThese
+    Director,
+    /// tef:presidentJury
+    President,
+    /// tef:membreJury
+    Member,
+    /// tef:rapporteur
+    Rapporteur,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Author => write!(f, "author"),
+            Role::Director => write!(f, "director"),
+            Role::President => write!(f, "president"),
+            Role::Member => write!(f, "member"),
+            Role::Rapporteur => write!(f, "rapporteur"),
+        }
+    }
+}
+
+/// Implement [`Authorities`] for types with both `autorite_interne` and `autorite_externe`, along with an
+/// inherent `resolve_authority` so callers don't need the trait in scope.
+macro_rules! authorities {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl Authorities for $type {
+                fn autorites_internes(&self) -> &[AutoriteInterne] {
+                    self.autorite_interne.as_deref().unwrap_or_default()
+                }
+
+                fn autorites_externes(&self) -> &[AutoriteExterne] {
+                    self.autorite_externe.as_deref().unwrap_or_default()
+                }
+            }
+
+            impl $type {
+                /// First `tef:MADSAuthority` of the thesis admin data this element points to
+                pub fn resolve_authority<'a>(&self, admin: &'a ThesisAdmin) -> Option<&'a MADSAuthority> {
+                    Authorities::resolve_authority(self, admin)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! person {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl Person for $type {
+                fn nom(&self) -> &Nom {
+                    &self.nom
+                }
+
+                fn prenom(&self) -> &Prenom {
+                    &self.prenom
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! organization {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl Organization for $type {
+                fn nom(&self) -> &Nom {
+                    &self.nom
+                }
+            }
+        )*
+    };
+}
+
+authorities!(
+    DirecteurThese,
+    PresidentJury,
+    MembreJury,
+    Rapporteur,
+    EcoleDoctorale,
+    PartenaireRecherche,
+    ThesisDegreeGrantor,
+    Editeur,
+);
+
+/// The author has no internal authority
+impl Authorities for Auteur {
+    fn autorites_internes(&self) -> &[AutoriteInterne] {
+        &[]
+    }
+
+    fn autorites_externes(&self) -> &[AutoriteExterne] {
+        self.autorite_externe.as_deref().unwrap_or_default()
+    }
+}
+
+person!(
+    Auteur,
+    DirecteurThese,
+    PresidentJury,
+    MembreJury,
+    Rapporteur
+);
+
+organization!(
+    EcoleDoctorale,
+    PartenaireRecherche,
+    ThesisDegreeGrantor,
+    Editeur
+);
+
+impl ThesisAdmin {
+    /// Every person of the thesis with their role, in the order of the schema
+    pub fn persons(&self) -> Vec<(Role, &dyn Person)> {
+        let mut persons: Vec<(Role, &dyn Person)> = vec![(Role::Author, &self.auteur)];
+
+        persons.extend(
+            self.directeur_these
+                .iter()
+                .map(|person| (Role::Director, person as &dyn Person)),
+        );
+        persons.extend(
+            self.president_jury
+                .iter()
+                .map(|person| (Role::President, person as &dyn Person)),
+        );
+        persons.extend(
+            self.membre_jury
+                .iter()
+                .map(|person| (Role::Member, person as &dyn Person)),
+        );
+        persons.extend(
+            self.rapporteur
+                .iter()
+                .map(|person| (Role::Rapporteur, person as &dyn Person)),
+        );
+
+        persons
+    }
+
+    /// Every organization of the thesis: grantors, doctoral schools and research partners
+    pub fn organizations(&self) -> Vec<&dyn Organization> {
+        let grantors = self
+            .thesis_degree
+            .grantor()
+            .iter()
+            .map(|organization| organization as &dyn Organization);
+        let ecoles = self
+            .ecole_doctorale
+            .iter()
+            .map(|organization| organization as &dyn Organization);
+        let partenaires = self
+            .partenaire_recherche
+            .iter()
+            .map(|organization| organization as &dyn Organization);
+
+        grantors.chain(ecoles).chain(partenaires).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::THESE;
+    use crate::parse_tef;
+
+    #[test]
+    fn mads_authority() {
+        let mets = parse_tef(THESE).unwrap();
+        let admin = mets.thesis_admin().unwrap();
+        let directeur = &admin.directeur_these()[0];
+
+        let authority = directeur.resolve_authority(admin).unwrap();
+        assert_eq!(authority.authority_id(), "MADS_DIRECTEUR_DE_THESE_1");
+        assert_eq!(authority.r#type(), "personal");
+        assert_eq!(
+            authority,
+            admin.authority(" MADS_DIRECTEUR_DE_THESE_1 ").unwrap()
+        );
+
+        let person = authority.value();
+        assert_eq!(person.family_name(), Some("de La Fontaine"));
+        assert_eq!(person.given_name(), Some("Marie"));
+        assert_eq!(person.name_part_of_type("termsOfAddress"), None);
+        assert_eq!(
+            person.description().as_ref().unwrap().value(),
+            "Professeure"
+        );
+
+        assert_eq!(directeur.ppn().unwrap().to_string(), "027189848");
+        // the author has no internal authority
+        assert!(admin.auteur().resolve_authority(admin).is_none());
+        assert!(admin
+            .president_jury()
+            .as_ref()
+            .unwrap()
+            .resolve_authority(admin)
+            .is_none());
+    }
+
+    #[test]
+    fn persons_and_organizations() {
+        let mets = parse_tef(THESE).unwrap();
+        let admin = mets.thesis_admin().unwrap();
+        let persons: Vec<(Role, String)> = admin
+            .persons()
+            .into_iter()
+            .map(|(role, person)| (role, person.sort_name()))
+            .collect();
+
+        assert_eq!(
+            persons,
+            [
+                (Role::Author, "Dupont, Jean-Pierre".to_string()),
+                (Role::Director, "de La Fontaine, Marie".to_string()),
+                (Role::President, "Martin, Paul".to_string()),
+                (Role::Member, "Durand, Anne".to_string()),
+                (Role::Rapporteur, "Van der Berg, Hans".to_string()),
+            ]
+        );
+        assert_eq!(Role::Rapporteur.to_string(), "rapporteur");
+
+        let organizations: Vec<(&str, Option<String>)> = admin
+            .organizations()
+            .into_iter()
+            .map(|organization| {
+                (
+                    organization.nom().0.as_str(),
+                    organization.ppn().map(|ppn| ppn.to_string()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            organizations,
+            [
+                ("Université de Lyon", Some("137964781".to_string())),
+                (
+                    "École doctorale de Physique et d'Astrophysique de Lyon",
+                    Some("135278384".to_string())
+                ),
+                ("Institut Lumière Matière", None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use super::*;
+use crate::date::TefDate;
+use crate::identifiers::{Nnt, NNT_TYPE};
+
+/// `dc:type` added when none is given
+const DEFAULT_TYPES: [(Option<&str>, &str); 2] = [
+    (None, "Electronic Thesis or Dissertation"),
+    (Some("dcterms:DCMIType"), "Text"),
+];
+
+macro_rules! person_new {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl $type {
+                pub fn new<N: Into<String>, P: Into<String>>(nom: N, prenom: P) -> Self {
+                    Self {
+                        nom: Nom(nom.into()),
+                        prenom: Prenom(prenom.into()),
+                        ..Default::default()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! organization_new {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl $type {
+                pub fn new<N: Into<String>>(nom: N) -> Self {
+                    Self {
+                        nom: Nom(nom.into()),
+                        ..Default::default()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// `with_autorite_externe` and, unless the type has no internal authority, `with_autorite_interne`
+macro_rules! with_authorities {
+    (@externe $($type:ty),*) => {
+        $(
+            impl $type {
+                /// Add a `tef:autoriteExterne`, e.g. `("sudoc", "026403447")`
+                pub fn with_autorite_externe<S: Into<String>, T: Into<String>>(
+                    mut self,
+                    source: S,
+                    id: T,
+                ) -> Self {
+                    self.autorite_externe
+                        .get_or_insert_with(Vec::new)
+                        .push(AutoriteExterne::new(source, id));
+                    self
+                }
+            }
+        )*
+    };
+    ($($type:ty),* $(,)?) => {
+        with_authorities!(@externe $($type),*);
+        $(
+            impl $type {
+                /// Add a `tef:autoriteInterne` pointing to a `tef:MADSAuthority`
+                pub fn with_autorite_interne<T: Into<String>>(mut self, id: T) -> Self {
+                    self.autorite_interne
+                        .get_or_insert_with(Vec::new)
+                        .push(AutoriteInterne(id.into()));
+                    self
+                }
+            }
+        )*
+    };
+}
+
+person_new!(
+    Auteur,
+    DirecteurThese,
+    PresidentJury,
+    MembreJury,
+    Rapporteur
+);
+
+organization_new!(EcoleDoctorale, ThesisDegreeGrantor);
+
+with_authorities!(@externe Auteur);
+
+with_authorities!(
+    DirecteurThese,
+    PresidentJury,
+    MembreJury,
+    Rapporteur,
+    EcoleDoctorale,
+    PartenaireRecherche,
+    ThesisDegreeGrantor,
+);
+
+impl PartenaireRecherche {
+    /// Research partner of a known type, use [`PartenaireRecherche::autre`] for other types
+    pub fn new<N: Into<String>>(r#type: PartenaireRechercheValues, nom: N) -> Self {
+        Self {
+            r#type,
+            nom: Nom(nom.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Research partner of type `autreType`, described by `autre_type`
+    pub fn autre<A: Into<String>, N: Into<String>>(autre_type: A, nom: N) -> Self {
+        Self {
+            r#type: PartenaireRechercheValues::AutreType,
+            autre_type: Some(autre_type.into()),
+            nom: Nom(nom.into()),
+            ..Default::default()
+        }
+    }
+}
+
+impl ThesisRecord {
+    pub fn builder() -> ThesisRecordBuilder {
+        ThesisRecordBuilder::default()
+    }
+}
+
+/// Builder of a `tef:thesisRecord`, see [`ThesisRecord::builder`]
+#[derive(Debug, Clone, Default)]
+pub struct ThesisRecordBuilder {
+    record: ThesisRecord,
+}
+
+impl ThesisRecordBuilder {
+    pub fn title<L: Into<String>, T: Into<String>>(mut self, lang: L, text: T) -> Self {
+        self.record.title = Title::new(lang, text);
+        self
+    }
+
+    pub fn alternative<L: Into<String>, T: Into<String>>(mut self, lang: L, text: T) -> Self {
+        self.record
+            .alternative
+            .get_or_insert_with(Vec::new)
+            .push(Alternative::new(lang, text));
+        self
+    }
+
+    pub fn subject<L: Into<String>, T: Into<String>>(mut self, lang: L, text: T) -> Self {
+        self.record
+            .subject
+            .get_or_insert_with(Vec::new)
+            .push(Subject::new(lang, text));
+        self
+    }
+
+    pub fn sujet_rameau(mut self, sujet_rameau: SujetRameau) -> Self {
+        self.record.sujet_rameau = Some(sujet_rameau);
+        self
+    }
+
+    pub fn r#abstract<L: Into<String>, T: Into<String>>(mut self, lang: L, text: T) -> Self {
+        self.record
+            .r#abstract
+            .get_or_insert_with(Vec::new)
+            .push(Abstract::new(lang, text));
+        self
+    }
+
+    /// Add a `dc:type`, `Electronic Thesis or Dissertation` and `Text` are used when none is given
+    pub fn r#type(mut self, r#type: Type) -> Self {
+        self.record.r#type.push(r#type);
+        self
+    }
+
+    /// Add a language code, e.g. `fr`
+    pub fn language<T: Into<String>>(mut self, code: T) -> Self {
+        self.record.language.push(Language::new(code));
+        self
+    }
+
+    pub fn coverage<T: Into<String>>(mut self, coverage: T) -> Self {
+        self.record
+            .coverage
+            .get_or_insert_with(Vec::new)
+            .push(Coverage(coverage.into()));
+        self
+    }
+
+    /// Check the mandatory elements and build the record
+    pub fn build(self) -> Result<ThesisRecord, Error> {
+        let mut record = self.record;
+
+        if record.title.text().trim().is_empty() {
+            return Err(Error::missing("thesisRecord/title"));
+        }
+        if record.r#abstract.as_deref().unwrap_or_default().is_empty() {
+            return Err(Error::missing("thesisRecord/abstract"));
+        }
+        if record.language.is_empty() {
+            return Err(Error::missing("thesisRecord/language"));
+        }
+        if record.r#type.is_empty() {
+            record.r#type = DEFAULT_TYPES
+                .iter()
+                .map(|(r#type, text)| match r#type {
+                    Some(r#type) => Type::typed(*r#type, *text),
+                    None => Type::new(*text),
+                })
+                .collect();
+        }
+
+        Ok(record)
+    }
+}
+
+impl ThesisAdmin {
+    pub fn builder() -> ThesisAdminBuilder {
+        ThesisAdminBuilder::default()
+    }
+}
+
+/// Builder of a `tef:thesisAdmin`, see [`ThesisAdmin::builder`]
+///
+/// `tef:theseSurTravaux` and `tef:avisJury` are `non` unless set.
+#[derive(Debug, Clone)]
+pub struct ThesisAdminBuilder {
+    admin: ThesisAdmin,
+}
+
+impl Default for ThesisAdminBuilder {
+    fn default() -> Self {
+        Self {
+            admin: ThesisAdmin {
+                these_sur_travaux: TheseSurTravaux("non".to_string()),
+                avis_jury: AvisJury("non".to_string()),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ThesisAdminBuilder {
+    pub fn auteur(mut self, auteur: Auteur) -> Self {
+        self.admin.auteur = auteur;
+        self
+    }
+
+    pub fn identifier(mut self, identifier: Identifier) -> Self {
+        self.admin.identifier.push(identifier);
+        self
+    }
+
+    /// Add the `dc:identifier` of type `tef:NNT`
+    pub fn nnt(self, nnt: &Nnt) -> Self {
+        self.identifier(Identifier::new(NNT_TYPE, nnt.as_str()))
+    }
+
+    /// Date of the defense
+    pub fn date_accepted(mut self, date: TefDate) -> Self {
+        self.admin.date_accepted = DateAccepted::new(date);
+        self
+    }
+
+    pub fn discipline<L: Into<String>, T: Into<String>>(mut self, lang: L, text: T) -> Self {
+        self.admin.thesis_degree.discipline = ThesisDegreeDiscipline {
+            lang: lang.into(),
+            text: text.into(),
+        };
+        self
+    }
+
+    /// Add an establishment of defense
+    pub fn grantor(mut self, grantor: ThesisDegreeGrantor) -> Self {
+        self.admin.thesis_degree.grantor.push(grantor);
+        self
+    }
+
+    pub fn level(mut self, level: ThesisDegreeLevelValues) -> Self {
+        self.admin.thesis_degree.level = ThesisDegreeLevel { text: level };
+        self
+    }
+
+    pub fn degree_name<T: Into<String>>(mut self, name: T) -> Self {
+        self.admin.thesis_degree.name = Some(ThesisDegreeName(name.into()));
+        self
+    }
+
+    /// `true` gives `oui`, `false` gives `non`
+    pub fn these_sur_travaux(mut self, value: bool) -> Self {
+        self.admin.these_sur_travaux = TheseSurTravaux(yes_no(value));
+        self
+    }
+
+    /// `true` gives `oui`, `false` gives `non`
+    pub fn avis_jury(mut self, value: bool) -> Self {
+        self.admin.avis_jury = AvisJury(yes_no(value));
+        self
+    }
+
+    pub fn directeur(mut self, directeur: DirecteurThese) -> Self {
+        self.admin.directeur_these.push(directeur);
+        self
+    }
+
+    pub fn president(mut self, president: PresidentJury) -> Self {
+        self.admin.president_jury = Some(president);
+        self
+    }
+
+    pub fn membre(mut self, membre: MembreJury) -> Self {
+        self.admin.membre_jury.push(membre);
+        self
+    }
+
+    pub fn rapporteur(mut self, rapporteur: Rapporteur) -> Self {
+        self.admin.rapporteur.push(rapporteur);
+        self
+    }
+
+    pub fn ecole_doctorale(mut self, ecole: EcoleDoctorale) -> Self {
+        self.admin.ecole_doctorale.push(ecole);
+        self
+    }
+
+    pub fn partenaire(mut self, partenaire: PartenaireRecherche) -> Self {
+        self.admin.partenaire_recherche.push(partenaire);
+        self
+    }
+
+    /// Add an OAI set, e.g. `ddc:530`
+    pub fn oai_set_spec<T: Into<String>>(mut self, set_spec: T) -> Self {
+        self.admin.oai_set_spec.push(OaiSetSpec(set_spec.into()));
+        self
+    }
+
+    pub fn mads_authority(mut self, authority: MADSAuthority) -> Self {
+        self.admin
+            .mads_authority
+            .get_or_insert_with(Vec::new)
+            .push(authority);
+        self
+    }
+
+    /// Check the mandatory elements and build the admin data
+    pub fn build(self) -> Result<ThesisAdmin, Error> {
+        let admin = self.admin;
+
+        if admin.auteur.nom.0.trim().is_empty() {
+            return Err(Error::missing("thesisAdmin/auteur/nom"));
+        }
+        if admin.identifier.is_empty() {
+            return Err(Error::missing("thesisAdmin/identifier"));
+        }
+        for identifier in admin
+            .identifier
+            .iter()
+            .filter(|identifier| identifier.r#type() == NNT_TYPE)
+        {
+            if let Err(err) = identifier.text().parse::<Nnt>() {
+                return Err(Error::invalid("thesisAdmin/identifier", err.to_string()));
+            }
+        }
+        if admin.date_accepted.text().trim().is_empty() {
+            return Err(Error::missing("thesisAdmin/dateAccepted"));
+        }
+        if admin.thesis_degree.discipline.text.trim().is_empty() {
+            return Err(Error::missing(
+                "thesisAdmin/thesis.degree/thesis.degree.discipline",
+            ));
+        }
+        if admin.thesis_degree.grantor.is_empty() {
+            return Err(Error::missing(
+                "thesisAdmin/thesis.degree/thesis.degree.grantor",
+            ));
+        }
+        if admin.directeur_these.is_empty() {
+            return Err(Error::missing("thesisAdmin/directeurThese"));
+        }
+        if admin.oai_set_spec.is_empty() {
+            return Err(Error::missing("thesisAdmin/oaiSetSpec"));
+        }
+
+        Ok(admin)
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    match value {
+        true => "oui",
+        false => "non",
+    }
+    .to_string()
+}
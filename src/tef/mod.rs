@@ -1,12 +1,22 @@
 use std::fmt::Display;
 
+use crate::date::TefDate;
+use crate::error::Error;
+use crate::identifiers::{Ppn, NNT_TYPE, PPN_SOURCES};
+use crate::raw::{deserialize_elements, Others, RawElement};
 use crate::{dc::*, dcterms::*};
-use getset::Getters;
+use getset::{Getters, MutGetters, Setters};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "lax")]
 use lax_derive::lax;
 
+mod agents;
+mod builder;
+
+pub use agents::{Authorities, Organization, Person, Role};
+pub use builder::{ThesisAdminBuilder, ThesisRecordBuilder};
+
 fn empty_string_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -31,17 +41,19 @@ where
 /// <http://www.abes.fr/abes/documents/tef>
 /// Auteur de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Auteur {
     nom: Nom,
     prenom: Prenom,
     #[serde(rename = "nomDeNaissance")]
     nom_de_naissance: Option<NomDeNaissance>,
     #[serde(rename = "dateNaissance")]
-    #[serde(deserialize_with = "empty_string_as_none")]
+    #[serde(default, deserialize_with = "empty_string_as_none")]
     date_naissance: Option<DateNaissance>,
-    #[serde(deserialize_with = "empty_string_as_none")]
+    #[serde(default, deserialize_with = "empty_string_as_none")]
     nationalite: Option<String>,
     #[serde(rename = "autoriteExterne")]
     autorite_externe: Option<Vec<AutoriteExterne>>,
@@ -52,8 +64,10 @@ pub struct Auteur {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Identifiant de notice d'autorité issue d'un système externe
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct AutoriteExterne {
     #[serde(rename = "@autoriteSource")]
     autorite_source: String,
@@ -61,6 +75,25 @@ pub struct AutoriteExterne {
     text: String,
 }
 
+impl AutoriteExterne {
+    pub fn new<S: Into<String>, T: Into<String>>(autorite_source: S, text: T) -> Self {
+        Self {
+            autorite_source: autorite_source.into(),
+            text: text.into(),
+        }
+    }
+
+    /// PPN of the record, when the source is the Sudoc or IdRef, see [`PPN_SOURCES`]
+    pub fn ppn(&self) -> Option<Ppn> {
+        let source = self.autorite_source.trim().to_lowercase();
+
+        match PPN_SOURCES.contains(&source.as_str()) {
+            true => self.text.parse().ok(),
+            false => None,
+        }
+    }
+}
+
 /// Autre format de fichier
 /// tef:autreFormatFichier
 /// <http://www.abes.fr/abes/documents/tef>
@@ -99,13 +132,21 @@ impl std::fmt::Display for DateNaissance {
     }
 }
 
+impl DateNaissance {
+    pub fn date(&self) -> Result<TefDate, Error> {
+        self.0.parse()
+    }
+}
+
 /// Directeur de thèse
 /// tef:directeurThèse
 /// <http://www.abes.fr/abes/documents/tef>
 /// Personne qui encadre et oriente le travail du doctorant
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct DirecteurThese {
     nom: Nom,
     prenom: Prenom,
@@ -120,8 +161,10 @@ pub struct DirecteurThese {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Ecole doctorale au sein de laquelle s’est déroulée la recherche du doctorant
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct EcoleDoctorale {
     nom: Nom,
     #[serde(rename = "autoriteInterne")]
@@ -135,8 +178,10 @@ pub struct EcoleDoctorale {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Organisme responsable de l’édition électronique de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Editeur {
     nom: Nom,
     place: Place,
@@ -150,14 +195,16 @@ pub struct Editeur {
 /// tef:edition
 /// <http://www.abes.fr/abes/documents/tef>
 /// Ensemble des métadonnées relatives à une édition électronique de la thèse
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Edition {
     #[serde(rename = "$value")]
     values: Vec<EditionValues>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum EditionValues {
     Medium(Medium),
@@ -166,13 +213,45 @@ pub enum EditionValues {
     Issued(Issued),
     Replaces(Replaces),
     Editeur(Editeur),
-    #[serde(other)]
-    Other,
+    Other(RawElement),
+}
+
+deserialize_elements!(EditionValues {
+    "medium" => Medium,
+    "extent" => Extent,
+    "identifier" => Identifier,
+    "issued" => Issued,
+    "replaces" => Replaces,
+    "editeur" => Editeur,
+});
+
+impl Others for Edition {
+    fn others(&self) -> Vec<&RawElement> {
+        self.values
+            .iter()
+            .filter_map(|value| match value {
+                EditionValues::Other(raw) => Some(raw),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn others_mut(&mut self) -> Vec<&mut RawElement> {
+        self.values
+            .iter_mut()
+            .filter_map(|value| match value {
+                EditionValues::Other(raw) => Some(raw),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct ElementdEntree {
     #[serde(rename = "@autoriteExterne")]
     autorite_externe: Option<String>,
@@ -187,8 +266,10 @@ pub struct ElementdEntree {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Type d'encodage du fichier
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Encodage {
     #[serde(rename = "$text")]
     text: EncodageValues,
@@ -208,8 +289,10 @@ pub enum EncodageValues {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Format du fichier
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct FormatFichier {
     #[serde(rename = "$text")]
     text: FormatFichierValues,
@@ -242,8 +325,10 @@ pub enum FormatFichierValues {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Données d'autorité minimales relatives à une personne (morale ou physique) mentionnée dans la notice
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct MADSAuthority {
     #[serde(rename = "@authorityID")]
     authority_id: String,
@@ -258,8 +343,10 @@ pub struct MADSAuthority {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Indique une partie manquante en cas de version incomplète.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Manque {
     #[serde(rename = "ressourceID")]
     ressource_id: Option<RessourceID>,
@@ -272,8 +359,10 @@ pub struct Manque {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Personne chargée d'évaluer une thèse au moment de sa soutenance
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct MembreJury {
     nom: Nom,
     prenom: Prenom,
@@ -288,8 +377,10 @@ pub struct MembreJury {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Ensemble des métadonnées techniques caractérisant un fichier informatique
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct MetaFichier {
     // divergeances entre le schéma et la réalité
     encodage: Option<Encodage>,
@@ -356,8 +447,10 @@ pub struct OaiSetSpec(#[serde(rename = "$text")] pub String);
 /// <http://www.abes.fr/abes/documents/tef>
 /// Entreprise ou organisme, public ou privé, ayant participé à la réalisation de la thèse par la mise à disposition de moyens.
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct PartenaireRecherche {
     #[serde(rename = "@type")]
     r#type: PartenaireRechercheValues,
@@ -370,7 +463,7 @@ pub struct PartenaireRecherche {
     autorite_externe: Option<Vec<AutoriteExterne>>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, Hash, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum PartenaireRechercheValues {
     Laboratoire,
@@ -378,15 +471,10 @@ pub enum PartenaireRechercheValues {
     Fondation,
     EquipeRecherche,
     #[serde(other)]
+    #[default]
     AutreType,
 }
 
-impl Default for PartenaireRechercheValues {
-    fn default() -> Self {
-        Self::AutreType
-    }
-}
-
 use super::mads::*;
 
 /// Personne
@@ -394,11 +482,32 @@ use super::mads::*;
 /// <http://www.abes.fr/abes/documents/tef>
 /// Données d'autorité minimales relatives à une personne (morale ou physique) mentionnée dans la notice
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct PersonMADS {
+    #[serde(rename = "namePart")]
     name_part: Vec<NamePart>,
-    description: Description,
+    description: Option<Description>,
+}
+
+impl PersonMADS {
+    /// Text of the first `mads:namePart` of the given type, e.g. `family` or `given`
+    pub fn name_part_of_type(&self, r#type: &str) -> Option<&str> {
+        self.name_part
+            .iter()
+            .find(|name_part| name_part.r#type() == r#type)
+            .map(|name_part| name_part.value().as_str())
+    }
+
+    pub fn family_name(&self) -> Option<&str> {
+        self.name_part_of_type("family")
+    }
+
+    pub fn given_name(&self) -> Option<&str> {
+        self.name_part_of_type("given")
+    }
 }
 
 /// Lieu d'édition
@@ -422,8 +531,10 @@ pub struct Prenom(#[serde(rename = "$text")] pub String);
 /// <http://www.abes.fr/abes/documents/tef>
 /// Personne présidant le jury lors de la soutenance de la thèse et chargée de donner l'avis du jury
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct PresidentJury {
     nom: Nom,
     prenom: Prenom,
@@ -438,8 +549,10 @@ pub struct PresidentJury {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Personne qui rend compte du contenu de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Rapporteur {
     nom: Nom,
     prenom: Prenom,
@@ -456,7 +569,7 @@ pub struct Rapporteur {
 #[cfg_attr(feature = "lax", lax)]
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
 // A pour enfant n'importe quel(s) élément(s) Dublin Core (simple et qualifié).
-pub struct RessourceExterneDescription(std::collections::HashMap<String, String>);
+pub struct RessourceExterneDescription(pub std::collections::HashMap<String, String>);
 
 /// Identifiant de ressource externe
 /// tef:ressourceID
@@ -475,8 +588,10 @@ pub struct RessourceID(#[serde(rename = "$text")] pub String);
 pub struct StructureFichier(#[serde(rename = "$text")] pub String);
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Subdivision {
     #[serde(rename = "@autoriteSource")]
     autorite_source: Option<String>,
@@ -493,8 +608,10 @@ pub struct Subdivision {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct SujetRameau {
     #[serde(rename = "$value")]
     values: Vec<Option<SujetRameauValues>>,
@@ -513,13 +630,76 @@ pub enum SujetRameauValues {
     VedetteRameauGenreForme(VedetteRameauGenreForme),
 }
 
+/// Evaluate an expression on the heading of any type
+macro_rules! vedette {
+    ($value:expr, $vedette:ident => $expr:expr) => {
+        match $value {
+            SujetRameauValues::VedetteRameauPersonne($vedette) => $expr,
+            SujetRameauValues::VedetteRameauCollectivite($vedette) => $expr,
+            SujetRameauValues::VedetteRameauFamille($vedette) => $expr,
+            SujetRameauValues::VedetteRameauAuteurTitre($vedette) => $expr,
+            SujetRameauValues::VedetteRameauTitre($vedette) => $expr,
+            SujetRameauValues::VedetteRameauNomCommun($vedette) => $expr,
+            SujetRameauValues::VedetteRameauNomGeographique($vedette) => $expr,
+            SujetRameauValues::VedetteRameauGenreForme($vedette) => $expr,
+        }
+    };
+}
+
+impl SujetRameauValues {
+    /// Name of the heading element, e.g. `vedetteRameauNomCommun`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SujetRameauValues::VedetteRameauPersonne(_) => "vedetteRameauPersonne",
+            SujetRameauValues::VedetteRameauCollectivite(_) => "vedetteRameauCollectivite",
+            SujetRameauValues::VedetteRameauFamille(_) => "vedetteRameauFamille",
+            SujetRameauValues::VedetteRameauAuteurTitre(_) => "vedetteRameauAuteurTitre",
+            SujetRameauValues::VedetteRameauTitre(_) => "vedetteRameauTitre",
+            SujetRameauValues::VedetteRameauNomCommun(_) => "vedetteRameauNomCommun",
+            SujetRameauValues::VedetteRameauNomGeographique(_) => "vedetteRameauNomGeographique",
+            SujetRameauValues::VedetteRameauGenreForme(_) => "vedetteRameauGenreForme",
+        }
+    }
+
+    pub fn element_entree(&self) -> &ElementdEntree {
+        vedette!(self, vedette => &vedette.element_entree)
+    }
+
+    pub fn element_entree_mut(&mut self) -> &mut ElementdEntree {
+        vedette!(self, vedette => &mut vedette.element_entree)
+    }
+
+    pub fn subdivisions(&self) -> &[Subdivision] {
+        vedette!(self, vedette => vedette.subdivision.as_deref().unwrap_or_default())
+    }
+
+    pub fn subdivisions_mut(&mut self) -> &mut Option<Vec<Subdivision>> {
+        vedette!(self, vedette => &mut vedette.subdivision)
+    }
+
+    /// Entry element and subdivisions, e.g. `Matériaux -- Thèses et écrits académiques`
+    pub fn heading(&self) -> String {
+        std::iter::once(self.element_entree().text())
+            .chain(
+                self.subdivisions()
+                    .iter()
+                    .map(|subdivision| subdivision.text()),
+            )
+            .map(|text| text.trim())
+            .collect::<Vec<&str>>()
+            .join(" -- ")
+    }
+}
+
 /// Vedette Rameau personne physique
 /// vedetteRameauPersonne
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau se rapportant à une personne physique sujet de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauPersonne {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -532,8 +712,10 @@ pub struct VedetteRameauPersonne {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau se rapportant à une personne morale ou à un congrès sujet de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauCollectivite {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -546,8 +728,10 @@ pub struct VedetteRameauCollectivite {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau se rapportant à une famille sujet de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauFamille {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -560,8 +744,10 @@ pub struct VedetteRameauFamille {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau se rapportant à une oeuvre et à son auteur
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauAuteurTitre {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -574,8 +760,10 @@ pub struct VedetteRameauAuteurTitre {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau se rapportant à une œuvre sujet de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauTitre {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -588,8 +776,10 @@ pub struct VedetteRameauTitre {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau se rapportant à une œuvre sujet de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauNomCommun {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -602,8 +792,10 @@ pub struct VedetteRameauNomCommun {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Vedette Rameau se rapportant à une entité géographique sujet de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauNomGeographique {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -612,8 +804,10 @@ pub struct VedetteRameauNomGeographique {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct VedetteRameauGenreForme {
     #[serde(rename = "elementdEntree")]
     element_entree: ElementdEntree,
@@ -642,8 +836,10 @@ pub struct TheseSurTravaux(#[serde(rename = "$text")] pub String);
 /// <http://www.abes.fr/abes/documents/tef>
 /// Ensemble des métadonnées spécifiques au diplôme obtenu à l’issue d’une soutenance de thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct ThesisDegree {
     #[serde(rename = "thesis.degree.discipline")]
     discipline: ThesisDegreeDiscipline,
@@ -670,8 +866,10 @@ pub struct ThesisDegree {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Discipline de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct ThesisDegreeDiscipline {
     #[serde(rename = "@lang")]
     lang: String,
@@ -684,8 +882,10 @@ pub struct ThesisDegreeDiscipline {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Etablissement de soutenance
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct ThesisDegreeGrantor {
     nom: Nom,
     #[serde(rename = "autoriteInterne")]
@@ -699,26 +899,34 @@ pub struct ThesisDegreeGrantor {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Type de doctorat obtenu
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct ThesisDegreeLevel {
     #[serde(rename = "$text")]
     text: ThesisDegreeLevelValues,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
 pub enum ThesisDegreeLevelValues {
     #[serde(rename = "Doctorat d'Etat")]
     DoctoratEtat,
     #[serde(rename = "Doctorat")]
+    #[default]
     Doctorat,
     #[serde(rename = "Doctorat de troisième cycle")]
     Doctorat3eCycle,
 }
 
-impl Default for ThesisDegreeLevelValues {
-    fn default() -> Self {
-        Self::Doctorat
+impl ThesisDegreeLevelValues {
+    /// Label of the level, as written in TEF
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThesisDegreeLevelValues::DoctoratEtat => "Doctorat d'Etat",
+            ThesisDegreeLevelValues::Doctorat => "Doctorat",
+            ThesisDegreeLevelValues::Doctorat3eCycle => "Doctorat de troisième cycle",
+        }
     }
 }
 
@@ -735,8 +943,10 @@ pub struct ThesisDegreeName(#[serde(rename = "$text")] pub String);
 /// <http://www.abes.fr/abes/documents/tef>
 /// Ensemble des métadonnées administratives de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct ThesisAdmin {
     auteur: Auteur,
     identifier: Vec<Identifier>,
@@ -752,16 +962,17 @@ pub struct ThesisAdmin {
     directeur_these: Vec<DirecteurThese>,
     #[serde(rename = "presidentJury")]
     president_jury: Option<PresidentJury>,
-    #[serde(rename = "membreJury")]
+    #[serde(rename = "membreJury", default)]
     membre_jury: Vec<MembreJury>,
+    #[serde(default)]
     rapporteur: Vec<Rapporteur>,
-    #[serde(rename = "ecoleDoctorale")]
+    #[serde(rename = "ecoleDoctorale", default)]
     ecole_doctorale: Vec<EcoleDoctorale>,
-    #[serde(rename = "partenaireRecherche")]
+    #[serde(rename = "partenaireRecherche", default)]
     partenaire_recherche: Vec<PartenaireRecherche>,
     #[serde(rename = "oaiSetSpec")]
     oai_set_spec: Vec<OaiSetSpec>,
-    #[serde(rename = "MADSAuthority ")]
+    #[serde(rename = "MADSAuthority")]
     mads_authority: Option<Vec<MADSAuthority>>,
     // step
     #[cfg(feature = "gestion")]
@@ -769,9 +980,29 @@ pub struct ThesisAdmin {
     vie: Vie,
 }
 
+impl ThesisAdmin {
+    /// Text of the `dc:identifier` of type `tef:NNT`
+    pub fn nnt(&self) -> Option<&str> {
+        self.identifier
+            .iter()
+            .find(|identifier| identifier.r#type() == NNT_TYPE)
+            .map(|identifier| identifier.text().trim())
+    }
+
+    /// `tef:MADSAuthority` block with the given `@authorityID`
+    pub fn authority(&self, id: &str) -> Option<&MADSAuthority> {
+        self.mads_authority
+            .iter()
+            .flatten()
+            .find(|authority| authority.authority_id == id.trim())
+    }
+}
+
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct DatePremiereInscriptionDoctorat {
     #[serde(rename = "@type")]
     r#type: Option<String>,
@@ -779,9 +1010,18 @@ pub struct DatePremiereInscriptionDoctorat {
     text: String,
 }
 
+impl DatePremiereInscriptionDoctorat {
+    /// Date, read according to its `xsi:type`
+    pub fn date(&self) -> Result<TefDate, Error> {
+        TefDate::parse_typed(&self.text, self.r#type.as_deref())
+    }
+}
+
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct DateInscriptionEtab {
     #[serde(rename = "@type")]
     r#type: Option<String>,
@@ -790,8 +1030,10 @@ pub struct DateInscriptionEtab {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Vie {
     #[serde(rename = "@derogationDixAns")]
     derogation_dix_ans: String,
@@ -804,14 +1046,46 @@ pub struct Vie {
 }
 
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 #[serde(rename_all = "camelCase")]
 pub struct SoutenancePrevue {
     date_prevue: Option<String>,
     heure_prevue: Option<String>,
     lieu_prevue: Option<String>,
-    // publiciteSoutenance
+    publicite_soutenance: Option<RawElement>,
+}
+
+#[cfg(feature = "gestion")]
+impl Others for ThesisAdmin {
+    fn others(&self) -> Vec<&RawElement> {
+        self.vie
+            .soutenance_prevue
+            .publicite_soutenance
+            .iter()
+            .collect()
+    }
+
+    fn others_mut(&mut self) -> Vec<&mut RawElement> {
+        self.vie
+            .soutenance_prevue
+            .publicite_soutenance
+            .iter_mut()
+            .collect()
+    }
+}
+
+impl SoutenancePrevue {
+    pub fn date(&self) -> Result<TefDate, Error> {
+        match &self.date_prevue {
+            Some(date) => date.parse(),
+            None => Err(Error::missing(
+                "mets/amdSec/techMD/mdWrap/xmlData/thesisAdmin/vie/soutenancePrevue/datePrevue",
+            )),
+        }
+    }
 }
 
 /// Description de la thèse
@@ -819,8 +1093,10 @@ pub struct SoutenancePrevue {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Ensemble des métadonnées descriptives relatives à la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct ThesisRecord {
     coverage: Option<Vec<Coverage>>,
     title: Title,
@@ -840,8 +1116,10 @@ pub struct ThesisRecord {
 /// <http://www.abes.fr/abes/documents/tef>
 /// Ensemble des métadonnées descriptives relatives à une version incomplète de la thèse
 #[cfg_attr(feature = "lax", lax)]
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters)]
-#[getset(get = "pub")]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, Default, PartialEq, Getters, MutGetters, Setters,
+)]
+#[getset(get = "pub", set = "pub", get_mut = "pub")]
 pub struct Version {
     manque: Vec<Manque>,
     replaces: Vec<Replaces>,
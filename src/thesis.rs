@@ -0,0 +1,521 @@
+use std::collections::HashMap;
+
+use getset::Getters;
+
+use crate::error::Error;
+use crate::mets::*;
+use crate::mets_rights::*;
+use crate::tef::*;
+
+/// Thesis described by a TEF record, as organized by the `mets:structMap`
+///
+/// The `mets:div` tree is resolved into typed entities: the thesis, its versions and, for each version, its
+/// editions and external resources. Each entity holds the metadata sections and files its `DMDID`, `ADMID` and
+/// `mets:fptr` point to. References to missing sections are skipped.
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct Thesis<'a> {
+    entity: ThesisEntity<'a>,
+}
+
+/// Metadata sections and files of an entity
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct Metadata<'a> {
+    /// `mets:div` of the entity
+    div: &'a Div,
+    /// Descriptive metadata, from `DMDID`
+    descriptive: Vec<&'a DmdSec>,
+    /// Administrative metadata, from `ADMID`
+    administrative: Vec<&'a TechMD>,
+    /// Rights metadata, from `ADMID`
+    rights: Vec<&'a RightsMD>,
+    /// Files, from the `mets:fptr` children of the div
+    files: Vec<&'a File>,
+}
+
+/// Thesis, `mets:div` of type `THESE`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct ThesisEntity<'a> {
+    metadata: Metadata<'a>,
+    versions: Vec<VersionEntity<'a>>,
+}
+
+/// Complete or incomplete version of the thesis, `mets:div` of type `VERSION_COMPLETE` or `VERSION_INCOMPLETE`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct VersionEntity<'a> {
+    metadata: Metadata<'a>,
+    editions: Vec<EditionEntity<'a>>,
+    external_resources: Vec<ExternalResourceEntity<'a>>,
+}
+
+/// Electronic edition of a version, `mets:div` of type `EDITION`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct EditionEntity<'a> {
+    metadata: Metadata<'a>,
+}
+
+/// Resource published outside of the electronic editions, `mets:div` of type `RESSOURCE_EXTERNE`
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct ExternalResourceEntity<'a> {
+    metadata: Metadata<'a>,
+}
+
+/// Entity represented by a `mets:div`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Thesis,
+    Version,
+    Edition,
+    ExternalResource,
+    Unknown,
+}
+
+/// Sections of the record by `ID`
+struct Index<'a> {
+    dmd_secs: HashMap<&'a str, &'a DmdSec>,
+    tech_mds: HashMap<&'a str, &'a TechMD>,
+    rights_mds: HashMap<&'a str, &'a RightsMD>,
+    files: HashMap<&'a str, &'a File>,
+}
+
+impl<'a> Thesis<'a> {
+    /// Resolve the entities of a record from its first `mets:div` describing a thesis
+    pub fn from_mets(mets: &'a Mets) -> Result<Self, Error> {
+        let index = Index::new(mets);
+
+        let div = mets
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                MetsValues::StructMap(struct_map) => Some(struct_map),
+                _ => None,
+            })
+            .flat_map(|struct_map| struct_map.value())
+            .find_map(|div| index.find(div, Kind::Thesis))
+            .ok_or_else(|| Error::missing("mets/structMap/div"))?;
+
+        Ok(Self {
+            entity: ThesisEntity {
+                metadata: index.metadata(div),
+                versions: index
+                    .children(div, Kind::Version)
+                    .into_iter()
+                    .map(|div| index.version(div))
+                    .collect(),
+            },
+        })
+    }
+
+    pub fn record(&self) -> Option<&'a ThesisRecord> {
+        self.entity.record()
+    }
+
+    pub fn admin(&self) -> Option<&'a ThesisAdmin> {
+        self.entity.admin()
+    }
+
+    pub fn versions(&self) -> &[VersionEntity<'a>] {
+        &self.entity.versions
+    }
+
+    /// Editions of every version
+    pub fn editions(&self) -> impl Iterator<Item = &EditionEntity<'a>> {
+        self.versions().iter().flat_map(|version| &version.editions)
+    }
+
+    /// External resources of every version
+    pub fn external_resources(&self) -> impl Iterator<Item = &ExternalResourceEntity<'a>> {
+        self.versions()
+            .iter()
+            .flat_map(|version| &version.external_resources)
+    }
+}
+
+impl<'a> Metadata<'a> {
+    /// Content of the descriptive metadata sections
+    pub fn descriptive_values(&self) -> impl Iterator<Item = &'a XmlDataValues> + '_ {
+        self.descriptive
+            .iter()
+            .map(|dmd_sec| dmd_sec.value().value().value())
+    }
+
+    /// Content of the administrative metadata sections
+    pub fn administrative_values(&self) -> impl Iterator<Item = &'a XmlDataValues> + '_ {
+        self.administrative
+            .iter()
+            .map(|tech_md| tech_md.value().value().value())
+    }
+
+    /// Rights declarations of the rights metadata sections
+    pub fn rights_declarations(&self) -> impl Iterator<Item = &'a RightsDeclarationMD> + '_ {
+        self.rights
+            .iter()
+            .filter_map(|rights_md| match rights_md.value().value().value() {
+                XmlDataValues::RightsDeclarationMD(rights) => Some(rights),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> ThesisEntity<'a> {
+    pub fn record(&self) -> Option<&'a ThesisRecord> {
+        self.metadata
+            .descriptive_values()
+            .find_map(|value| match value {
+                XmlDataValues::ThesisRecord(record) => Some(record),
+                _ => None,
+            })
+    }
+
+    pub fn admin(&self) -> Option<&'a ThesisAdmin> {
+        self.metadata
+            .administrative_values()
+            .find_map(|value| match value {
+                XmlDataValues::ThesisAdmin(admin) => Some(admin),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> VersionEntity<'a> {
+    /// Description of an incomplete version, complete versions are described by the thesis record
+    pub fn version(&self) -> Option<&'a Version> {
+        self.metadata
+            .descriptive_values()
+            .find_map(|value| match value {
+                XmlDataValues::Version(version) => Some(version),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> EditionEntity<'a> {
+    pub fn edition(&self) -> Option<&'a Edition> {
+        self.metadata
+            .descriptive_values()
+            .find_map(|value| match value {
+                XmlDataValues::Edition(edition) => Some(edition),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> ExternalResourceEntity<'a> {
+    pub fn description(&self) -> Option<&'a RessourceExterneDescription> {
+        self.metadata
+            .descriptive_values()
+            .find_map(|value| match value {
+                XmlDataValues::RessourceExterneDescription(description) => Some(description),
+                _ => None,
+            })
+    }
+}
+
+impl<'a> Index<'a> {
+    fn new(mets: &'a Mets) -> Self {
+        let mut index = Self {
+            dmd_secs: HashMap::new(),
+            tech_mds: HashMap::new(),
+            rights_mds: HashMap::new(),
+            files: HashMap::new(),
+        };
+
+        for value in mets.values() {
+            match value {
+                MetsValues::DmdSec(dmd_sec) => {
+                    index.dmd_secs.insert(dmd_sec.id(), dmd_sec);
+                }
+                MetsValues::AmdSec(amd_sec) => {
+                    for value in amd_sec.values() {
+                        match value {
+                            AmdSecValues::TechMD(tech_md) => {
+                                index.tech_mds.insert(tech_md.id(), tech_md);
+                            }
+                            AmdSecValues::RightsMD(rights_md) => {
+                                if let Some(id) = rights_md.id() {
+                                    index.rights_mds.insert(id, rights_md);
+                                }
+                            }
+                            AmdSecValues::Other(_) => {}
+                        }
+                    }
+                }
+                MetsValues::FileSec(file_sec) => {
+                    file_sec
+                        .value()
+                        .iter()
+                        .for_each(|file_grp| index.insert_files(file_grp));
+                }
+                _ => {}
+            }
+        }
+
+        index
+    }
+
+    fn insert_files(&mut self, file_grp: &'a FileGrp) {
+        for value in file_grp.values() {
+            match value {
+                FileGrpValues::File(file) => {
+                    self.files.insert(file.id(), file);
+                }
+                FileGrpValues::FileGrp(file_grp) => self.insert_files(file_grp),
+            }
+        }
+    }
+
+    /// Kind of entity, from the `TYPE` of the div or else from its descriptive metadata
+    fn kind(&self, div: &Div) -> Kind {
+        let r#type = div.r#type().to_uppercase();
+
+        if r#type == "THESE" || r#type == "THESIS" {
+            return Kind::Thesis;
+        } else if r#type.starts_with("VERSION") {
+            return Kind::Version;
+        } else if r#type.starts_with("EDITION") {
+            return Kind::Edition;
+        } else if r#type.starts_with("RESSOURCE") {
+            return Kind::ExternalResource;
+        }
+
+        ids(div.dmdid())
+            .filter_map(|id| self.dmd_secs.get(id))
+            .find_map(|dmd_sec| match dmd_sec.value().value().value() {
+                XmlDataValues::ThesisRecord(_) => Some(Kind::Thesis),
+                XmlDataValues::Version(_) => Some(Kind::Version),
+                XmlDataValues::Edition(_) => Some(Kind::Edition),
+                XmlDataValues::RessourceExterneDescription(_) => Some(Kind::ExternalResource),
+                _ => None,
+            })
+            .unwrap_or(Kind::Unknown)
+    }
+
+    /// First div of the given kind, depth first
+    fn find(&self, div: &'a Div, kind: Kind) -> Option<&'a Div> {
+        if self.kind(div) == kind {
+            return Some(div);
+        }
+
+        sub_divs(div).find_map(|div| self.find(div, kind))
+    }
+
+    /// Divs of the given kind below a div, looking through the divs of unknown kind
+    fn children(&self, div: &'a Div, kind: Kind) -> Vec<&'a Div> {
+        sub_divs(div)
+            .flat_map(|div| match self.kind(div) {
+                found if found == kind => vec![div],
+                Kind::Unknown => self.children(div, kind),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    fn metadata(&self, div: &'a Div) -> Metadata<'a> {
+        let admids: Vec<&str> = ids(div.amdid()).collect();
+
+        Metadata {
+            div,
+            descriptive: ids(div.dmdid())
+                .filter_map(|id| self.dmd_secs.get(id).copied())
+                .collect(),
+            administrative: admids
+                .iter()
+                .filter_map(|id| self.tech_mds.get(id).copied())
+                .collect(),
+            rights: admids
+                .iter()
+                .filter_map(|id| self.rights_mds.get(id).copied())
+                .collect(),
+            files: div
+                .values()
+                .iter()
+                .filter_map(|value| match value {
+                    DivValues::Fptr(fptr) => fptr.fileid().as_deref(),
+                    DivValues::Div(_) => None,
+                })
+                .filter_map(|id| self.files.get(id).copied())
+                .collect(),
+        }
+    }
+
+    fn version(&self, div: &'a Div) -> VersionEntity<'a> {
+        VersionEntity {
+            metadata: self.metadata(div),
+            editions: self
+                .children(div, Kind::Edition)
+                .into_iter()
+                .map(|div| EditionEntity {
+                    metadata: self.metadata(div),
+                })
+                .collect(),
+            external_resources: self
+                .children(div, Kind::ExternalResource)
+                .into_iter()
+                .map(|div| ExternalResourceEntity {
+                    metadata: self.metadata(div),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn sub_divs(div: &Div) -> impl Iterator<Item = &Div> {
+    div.values().iter().filter_map(|value| match value {
+        DivValues::Div(div) => Some(div.as_ref()),
+        DivValues::Fptr(_) => None,
+    })
+}
+
+/// Identifiers of an `IDREFS` attribute
+fn ids(value: &Option<String>) -> impl Iterator<Item = &str> {
+    value.iter().flat_map(|value| value.split_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{these_with, THESE};
+    use crate::parse_tef;
+
+    /// The fixture with another structMap and the sections it points to
+    fn with_struct_map(struct_map: &str) -> Mets {
+        let start = THESE.find("  <mets:structMap").unwrap();
+        let input = format!("{}{}</mets:mets>", &THESE[..start], struct_map);
+        let input = input.replace(
+            "  <mets:amdSec>",
+            r#"  <mets:dmdSec ID="desc_edition_2">
+    <mets:mdWrap MDTYPE="OTHER" OTHERMDTYPE="tef_desc_edition">
+      <mets:xmlData>
+        <tef:edition>
+          <dcterms:medium xsi:type="dcterms:IMT">application/epub+zip</dcterms:medium>
+        </tef:edition>
+      </mets:xmlData>
+    </mets:mdWrap>
+  </mets:dmdSec>
+  <mets:dmdSec ID="desc_ressource">
+    <mets:mdWrap MDTYPE="OTHER" OTHERMDTYPE="tef_desc_ressource">
+      <mets:xmlData>
+        <tef:ressourceExterneDescription>
+          <tef:ressourceID>donnees</tef:ressourceID>
+        </tef:ressourceExterneDescription>
+      </mets:xmlData>
+    </mets:mdWrap>
+  </mets:dmdSec>
+  <mets:amdSec>"#,
+        );
+
+        parse_tef(&input).unwrap()
+    }
+
+    fn ids<'a>(divs: impl Iterator<Item = &'a Metadata<'a>>) -> Vec<&'a str> {
+        divs.map(|metadata| metadata.div().id().as_deref().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn fixture() {
+        let mets = parse_tef(THESE).unwrap();
+        let thesis = Thesis::from_mets(&mets).unwrap();
+        let metadata = thesis.entity().metadata();
+
+        assert!(thesis.record().is_some());
+        assert!(thesis.admin().is_some());
+        assert_eq!(metadata.rights_declarations().count(), 1);
+        assert_eq!(thesis.versions().len(), 1);
+        assert!(thesis.versions()[0].version().is_none());
+
+        let editions: Vec<&EditionEntity> = thesis.editions().collect();
+        assert_eq!(editions.len(), 1);
+        assert!(editions[0].edition().is_some());
+        assert_eq!(editions[0].metadata().files()[0].id(), "file_1");
+        assert_eq!(thesis.external_resources().count(), 0);
+    }
+
+    #[test]
+    fn kinds() {
+        // the second edition and the external resource are only known by their descriptive metadata, the
+        // version is below a div of unknown kind
+        let mets = with_struct_map(
+            r#"<mets:structMap TYPE="logical">
+    <mets:div ID="these" TYPE="THESE" DMDID="desc_these">
+      <mets:div ID="groupe" TYPE="GROUPE">
+        <mets:div ID="version_complete" TYPE="VERSION_COMPLETE">
+          <mets:div ID="edition_1" TYPE="EDITION" DMDID="desc_edition">
+            <mets:fptr FILEID="file_1"/>
+          </mets:div>
+          <mets:div ID="edition_2" TYPE="autre" DMDID="desc_edition_2"/>
+          <mets:div ID="ressource" TYPE="autre" DMDID="desc_ressource"/>
+        </mets:div>
+      </mets:div>
+    </mets:div>
+  </mets:structMap>
+"#,
+        );
+        let thesis = Thesis::from_mets(&mets).unwrap();
+
+        assert_eq!(
+            ids(thesis.versions().iter().map(|version| version.metadata())),
+            ["version_complete"]
+        );
+        assert_eq!(
+            ids(thesis.editions().map(|edition| edition.metadata())),
+            ["edition_1", "edition_2"]
+        );
+        assert_eq!(
+            ids(thesis
+                .external_resources()
+                .map(|resource| resource.metadata())),
+            ["ressource"]
+        );
+
+        let resource = thesis.external_resources().next().unwrap();
+        assert_eq!(
+            resource.description().unwrap().0.get("ressourceID"),
+            Some(&"donnees".to_string())
+        );
+    }
+
+    #[test]
+    fn thesis_kind_from_record() {
+        let mets = parse_tef(&these_with(&[(
+            r#"<mets:div ID="these" TYPE="THESE""#,
+            r#"<mets:div ID="these" TYPE="notice""#,
+        )]))
+        .unwrap();
+        let thesis = Thesis::from_mets(&mets).unwrap();
+
+        assert_eq!(thesis.entity().metadata().div().r#type(), "notice");
+        assert!(thesis.record().is_some());
+        assert_eq!(thesis.editions().count(), 1);
+    }
+
+    #[test]
+    fn missing_references() {
+        let mets = parse_tef(&these_with(&[
+            (
+                r#"DMDID="desc_these" ADMID="admin_these droits_these""#,
+                r#"DMDID="absent desc_these" ADMID="admin_these absent droits_these""#,
+            ),
+            (
+                r#"<mets:fptr FILEID="file_1"/>"#,
+                r#"<mets:fptr FILEID="absent"/><mets:fptr FILEID="file_1"/>"#,
+            ),
+        ]))
+        .unwrap();
+        let thesis = Thesis::from_mets(&mets).unwrap();
+        let metadata = thesis.entity().metadata();
+
+        assert_eq!(metadata.descriptive().len(), 1);
+        assert_eq!(metadata.administrative().len(), 1);
+        assert_eq!(metadata.rights().len(), 1);
+        assert!(thesis.record().is_some());
+
+        let edition = thesis.editions().next().unwrap();
+        assert_eq!(edition.metadata().files().len(), 1);
+    }
+}
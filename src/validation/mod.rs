@@ -0,0 +1,749 @@
+use std::fmt::Display;
+
+use getset::Getters;
+
+use crate::dc::*;
+use crate::dcterms::*;
+use crate::error::Error;
+use crate::identifiers::{Nnt, Ppn, NNT_TYPE, PPN_SOURCES};
+use crate::mets::*;
+use crate::tef::*;
+
+mod references;
+
+pub use references::check_references;
+
+/// Severity of a rule violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The record doesn't follow a recommendation of the profile
+    Warning,
+    /// The record breaks a mandatory rule of the profile
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Rule of the TEF profile broken by a record
+///
+/// The path follows the same convention as [`crate::Location`].
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct Violation {
+    severity: Severity,
+    path: String,
+    message: String,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.path, self.message)
+    }
+}
+
+/// Every violation found in a record, in document order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct ValidationReport {
+    violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    /// Whether the record breaks no mandatory rule, warnings are allowed
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Violation> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Violation> {
+        self.with_severity(Severity::Warning)
+    }
+
+    /// First broken mandatory rule as an [`Error::Validation`], warnings are allowed
+    pub fn into_result(self) -> Result<(), Error> {
+        match self.errors().next() {
+            Some(violation) => Err(Error::invalid(&violation.path, &violation.message)),
+            None => Ok(()),
+        }
+    }
+
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Violation> {
+        self.violations
+            .iter()
+            .filter(move |violation| violation.severity == severity)
+    }
+
+    pub(crate) fn push<P: Into<String>, M: Into<String>>(
+        &mut self,
+        severity: Severity,
+        path: P,
+        message: M,
+    ) {
+        self.violations.push(Violation {
+            severity,
+            path: path.into(),
+            message: message.into(),
+        })
+    }
+
+    pub(crate) fn error<P: Into<String>, M: Into<String>>(&mut self, path: P, message: M) {
+        self.push(Severity::Error, path, message)
+    }
+
+    pub(crate) fn warning<P: Into<String>, M: Into<String>>(&mut self, path: P, message: M) {
+        self.push(Severity::Warning, path, message)
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for violation in &self.violations {
+            writeln!(f, "{}", violation)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl IntoIterator for ValidationReport {
+    type Item = Violation;
+    type IntoIter = std::vec::IntoIter<Violation>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.violations.into_iter()
+    }
+}
+
+/// Check a record against the cardinalities of the TEF profile and the integrity of its METS identifiers, see
+/// [`check_references`].
+///
+/// Every violation is reported, the validation doesn't stop at the first one.
+pub fn validate(mets: &Mets) -> ValidationReport {
+    let mut validator = Validator::default();
+    validator.mets(mets);
+
+    let mut report = validator.report;
+    report.violations.extend(check_references(mets));
+    report
+}
+
+/// Path of a child element, with its position when it has siblings of the same name.
+pub(crate) fn child(parent: &str, name: &str, index: usize, count: usize) -> String {
+    if count > 1 {
+        format!("{}/{}[{}]", parent, name, index + 1)
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+/// Call `f` with the path of each element of a repeated child
+fn each<T, F>(parent: &str, name: &str, values: &[T], mut f: F)
+where
+    F: FnMut(String, &T),
+{
+    for (index, value) in values.iter().enumerate() {
+        f(child(parent, name, index, values.len()), value)
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    report: ValidationReport,
+    thesis_records: usize,
+    thesis_admins: usize,
+}
+
+impl Validator {
+    fn required(&mut self, path: String, value: &str) {
+        if value.trim().is_empty() {
+            self.report.error(path, "mandatory value is empty");
+        }
+    }
+
+    fn cardinality(&mut self, path: String, count: usize, min: usize, max: Option<usize>) {
+        match max {
+            _ if count < min && min == 1 => self.report.error(path, "mandatory element is missing"),
+            _ if count < min => self.report.error(
+                path,
+                format!("expected at least {} occurrences, found {}", min, count),
+            ),
+            Some(max) if count > max => self.report.error(
+                path,
+                format!("expected at most {} occurrences, found {}", max, count),
+            ),
+            _ => {}
+        }
+    }
+
+    fn xsi_type(&mut self, path: String, found: &str, expected: &str) {
+        if found != expected {
+            self.report.warning(
+                path,
+                format!("expected xsi:type \"{}\", found \"{}\"", expected, found),
+            );
+        }
+    }
+
+    fn mets(&mut self, mets: &Mets) {
+        let root = "mets";
+        let count = |f: fn(&MetsValues) -> bool| mets.values().iter().filter(|v| f(v)).count();
+        let dmd_secs = count(|value| matches!(value, MetsValues::DmdSec(_)));
+        let amd_secs = count(|value| matches!(value, MetsValues::AmdSec(_)));
+        let (mut dmd_index, mut amd_index) = (0, 0);
+
+        for value in mets.values() {
+            match value {
+                MetsValues::DmdSec(dmd_sec) => {
+                    let path = child(root, "dmdSec", dmd_index, dmd_secs);
+                    dmd_index += 1;
+                    self.md_wrap(&format!("{}/mdWrap", path), dmd_sec.value());
+                }
+                MetsValues::AmdSec(amd_sec) => {
+                    let path = child(root, "amdSec", amd_index, amd_secs);
+                    amd_index += 1;
+                    self.amd_sec(&path, amd_sec);
+                }
+                _ => {}
+            }
+        }
+
+        self.cardinality(
+            format!("{}/dmdSec/mdWrap/xmlData/thesisRecord", root),
+            self.thesis_records,
+            1,
+            Some(1),
+        );
+        self.cardinality(
+            format!("{}/amdSec/techMD/mdWrap/xmlData/thesisAdmin", root),
+            self.thesis_admins,
+            1,
+            Some(1),
+        );
+    }
+
+    fn amd_sec(&mut self, path: &str, amd_sec: &AmdSec) {
+        let tech_mds: Vec<&TechMD> = amd_sec
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                AmdSecValues::TechMD(tech_md) => Some(tech_md),
+                _ => None,
+            })
+            .collect();
+
+        each(path, "techMD", &tech_mds, |path, tech_md| {
+            self.md_wrap(&format!("{}/mdWrap", path), tech_md.value())
+        });
+    }
+
+    fn md_wrap(&mut self, path: &str, md_wrap: &MdWrap) {
+        let path = format!("{}/xmlData", path);
+
+        match md_wrap.value().value() {
+            XmlDataValues::ThesisRecord(record) => {
+                self.thesis_records += 1;
+                self.thesis_record(&format!("{}/thesisRecord", path), record)
+            }
+            XmlDataValues::ThesisAdmin(admin) => {
+                self.thesis_admins += 1;
+                self.thesis_admin(&format!("{}/thesisAdmin", path), admin)
+            }
+            XmlDataValues::Version(version) => self.version(&format!("{}/version", path), version),
+            XmlDataValues::Edition(edition) => self.edition(&format!("{}/edition", path), edition),
+            XmlDataValues::MetaFichier(meta) => {
+                self.meta_fichier(&format!("{}/meta_fichier", path), meta)
+            }
+            _ => {}
+        }
+    }
+
+    fn thesis_record(&mut self, path: &str, record: &ThesisRecord) {
+        let title = record.title();
+        self.required(format!("{}/title", path), title.text());
+        self.required(format!("{}/title/@lang", path), title.lang());
+
+        each(
+            path,
+            "alternative",
+            record.alternative().as_deref().unwrap_or_default(),
+            |path, alternative| {
+                self.required(path.clone(), alternative.text());
+                self.required(format!("{}/@lang", path), alternative.lang());
+            },
+        );
+
+        each(
+            path,
+            "subject",
+            record.subject().as_deref().unwrap_or_default(),
+            |path, subject| {
+                self.required(path.clone(), subject.text());
+                if subject.lang().is_none() {
+                    self.report
+                        .error(format!("{}/@lang", path), "mandatory attribute is missing");
+                }
+            },
+        );
+
+        if let Some(sujet_rameau) = record.sujet_rameau() {
+            self.sujet_rameau(&format!("{}/sujetRameau", path), sujet_rameau);
+        }
+
+        let abstracts = record.r#abstract().as_deref().unwrap_or_default();
+        self.cardinality(format!("{}/abstract", path), abstracts.len(), 1, None);
+        each(path, "abstract", abstracts, |path, r#abstract| {
+            self.required(path.clone(), r#abstract.text());
+            self.required(format!("{}/@lang", path), r#abstract.lang());
+        });
+
+        self.cardinality(format!("{}/type", path), record.r#type().len(), 1, None);
+        each(path, "type", record.r#type(), |path, r#type| {
+            self.required(path, r#type.text())
+        });
+
+        self.cardinality(
+            format!("{}/language", path),
+            record.language().len(),
+            1,
+            None,
+        );
+        each(path, "language", record.language(), |path, language| {
+            self.required(path.clone(), language.text());
+            self.xsi_type(
+                format!("{}/@type", path),
+                language.r#type(),
+                "dcterms:RFC3066",
+            );
+        });
+    }
+
+    fn sujet_rameau(&mut self, path: &str, sujet_rameau: &SujetRameau) {
+        let values: Vec<&SujetRameauValues> = sujet_rameau.values().iter().flatten().collect();
+
+        if values.is_empty() {
+            self.report.error(path, "expected at least one heading");
+        }
+
+        for value in values {
+            self.required(
+                format!("{}/{}/elementdEntree", path, value.name()),
+                value.element_entree().text(),
+            );
+        }
+    }
+
+    fn thesis_admin(&mut self, path: &str, admin: &ThesisAdmin) {
+        let auteur = admin.auteur();
+        self.required(format!("{}/auteur/nom", path), &auteur.nom().0);
+        self.required(format!("{}/auteur/prenom", path), &auteur.prenom().0);
+        self.autorites_externes(
+            &format!("{}/auteur", path),
+            auteur.autorite_externe().as_deref().unwrap_or_default(),
+        );
+
+        self.cardinality(
+            format!("{}/identifier", path),
+            admin.identifier().len(),
+            1,
+            None,
+        );
+        each(
+            path,
+            "identifier",
+            admin.identifier(),
+            |path, identifier| {
+                self.required(path.clone(), identifier.text());
+                if identifier.r#type() == NNT_TYPE && !identifier.text().trim().is_empty() {
+                    if let Err(err) = identifier.text().parse::<Nnt>() {
+                        self.report.error(path, err.to_string());
+                    }
+                }
+            },
+        );
+        if !admin.identifier().is_empty()
+            && !admin
+                .identifier()
+                .iter()
+                .any(|identifier| identifier.r#type() == NNT_TYPE)
+        {
+            self.report.warning(
+                format!("{}/identifier", path),
+                "no identifier of type tef:NNT",
+            );
+        }
+
+        let date_accepted = admin.date_accepted();
+        self.required(format!("{}/dateAccepted", path), date_accepted.text());
+        self.xsi_type(
+            format!("{}/dateAccepted/@type", path),
+            date_accepted.r#type(),
+            "dcterms:W3CDTF",
+        );
+
+        let degree = admin.thesis_degree();
+        let degree_path = format!("{}/thesis.degree", path);
+        self.required(
+            format!("{}/thesis.degree.discipline", degree_path),
+            degree.discipline().text(),
+        );
+        self.required(
+            format!("{}/thesis.degree.discipline/@lang", degree_path),
+            degree.discipline().lang(),
+        );
+        self.cardinality(
+            format!("{}/thesis.degree.grantor", degree_path),
+            degree.grantor().len(),
+            1,
+            None,
+        );
+        each(
+            &degree_path,
+            "thesis.degree.grantor",
+            degree.grantor(),
+            |path, grantor| {
+                self.required(format!("{}/nom", path), &grantor.nom().0);
+                self.autorites_externes(
+                    &path,
+                    grantor.autorite_externe().as_deref().unwrap_or_default(),
+                );
+            },
+        );
+
+        self.yes_no(
+            format!("{}/theseSurTravaux", path),
+            &admin.these_sur_travaux().0,
+        );
+        self.yes_no(format!("{}/avisJury", path), &admin.avis_jury().0);
+
+        self.cardinality(
+            format!("{}/directeurThese", path),
+            admin.directeur_these().len(),
+            1,
+            None,
+        );
+        each(
+            path,
+            "directeurThese",
+            admin.directeur_these(),
+            |path, person| {
+                self.required(format!("{}/nom", path), &person.nom().0);
+                self.required(format!("{}/prenom", path), &person.prenom().0);
+                self.autorites_externes(
+                    &path,
+                    person.autorite_externe().as_deref().unwrap_or_default(),
+                );
+            },
+        );
+
+        if let Some(person) = admin.president_jury() {
+            let path = format!("{}/presidentJury", path);
+            self.required(format!("{}/nom", path), &person.nom().0);
+            self.required(format!("{}/prenom", path), &person.prenom().0);
+            self.autorites_externes(
+                &path,
+                person.autorite_externe().as_deref().unwrap_or_default(),
+            );
+        }
+
+        each(path, "membreJury", admin.membre_jury(), |path, person| {
+            self.required(format!("{}/nom", path), &person.nom().0);
+            self.required(format!("{}/prenom", path), &person.prenom().0);
+            self.autorites_externes(
+                &path,
+                person.autorite_externe().as_deref().unwrap_or_default(),
+            );
+        });
+
+        each(path, "rapporteur", admin.rapporteur(), |path, person| {
+            self.required(format!("{}/nom", path), &person.nom().0);
+            self.required(format!("{}/prenom", path), &person.prenom().0);
+            self.autorites_externes(
+                &path,
+                person.autorite_externe().as_deref().unwrap_or_default(),
+            );
+        });
+
+        each(
+            path,
+            "ecoleDoctorale",
+            admin.ecole_doctorale(),
+            |path, ecole| {
+                self.required(format!("{}/nom", path), &ecole.nom().0);
+                self.autorites_externes(
+                    &path,
+                    ecole.autorite_externe().as_deref().unwrap_or_default(),
+                );
+            },
+        );
+
+        each(
+            path,
+            "partenaireRecherche",
+            admin.partenaire_recherche(),
+            |path, partenaire| {
+                self.required(format!("{}/nom", path), &partenaire.nom().0);
+                if partenaire.r#type() == &PartenaireRechercheValues::AutreType
+                    && partenaire
+                        .autre_type()
+                        .as_deref()
+                        .unwrap_or_default()
+                        .trim()
+                        .is_empty()
+                {
+                    self.report.error(
+                        format!("{}/@autreType", path),
+                        "mandatory when the type is autreType",
+                    );
+                }
+                self.autorites_externes(
+                    &path,
+                    partenaire.autorite_externe().as_deref().unwrap_or_default(),
+                );
+            },
+        );
+
+        self.cardinality(
+            format!("{}/oaiSetSpec", path),
+            admin.oai_set_spec().len(),
+            1,
+            None,
+        );
+        each(
+            path,
+            "oaiSetSpec",
+            admin.oai_set_spec(),
+            |path, set_spec| self.required(path, &set_spec.0),
+        );
+
+        each(
+            path,
+            "MADSAuthority",
+            admin.mads_authority().as_deref().unwrap_or_default(),
+            |path, authority| {
+                self.required(format!("{}/@authorityID", path), authority.authority_id());
+                self.required(format!("{}/@type", path), authority.r#type());
+            },
+        );
+    }
+
+    fn autorites_externes(&mut self, path: &str, autorites: &[AutoriteExterne]) {
+        each(path, "autoriteExterne", autorites, |path, autorite| {
+            self.required(path.clone(), autorite.text());
+            self.required(
+                format!("{}/@autoriteSource", path),
+                autorite.autorite_source(),
+            );
+
+            let source = autorite.autorite_source().trim().to_lowercase();
+            if PPN_SOURCES.contains(&source.as_str()) && !autorite.text().trim().is_empty() {
+                if let Err(err) = autorite.text().parse::<Ppn>() {
+                    self.report.error(path, err.to_string());
+                }
+            }
+        });
+    }
+
+    fn yes_no(&mut self, path: String, value: &str) {
+        match value.trim() {
+            "oui" | "non" => {}
+            "" => self.report.error(path, "mandatory value is empty"),
+            value => self.report.error(
+                path,
+                format!("expected \"oui\" or \"non\", found \"{}\"", value),
+            ),
+        }
+    }
+
+    fn version(&mut self, path: &str, version: &Version) {
+        each(path, "manque", version.manque(), |path, manque| {
+            if manque.ressource_id().is_none() && manque.note_version().is_none() {
+                self.report
+                    .warning(path, "expected a ressourceID or a noteVersion");
+            }
+        });
+
+        each(path, "replaces", version.replaces(), |path, replaces| {
+            self.required(path, replaces.text())
+        });
+    }
+
+    fn edition(&mut self, path: &str, edition: &Edition) {
+        let mut media: Vec<&Medium> = vec![];
+        let mut extents: Vec<&Extent> = vec![];
+        let mut identifiers: Vec<&Identifier> = vec![];
+        let mut issued: Vec<&Issued> = vec![];
+        let mut editeurs: Vec<&Editeur> = vec![];
+
+        for value in edition.values() {
+            match value {
+                EditionValues::Medium(value) => media.push(value),
+                EditionValues::Extent(value) => extents.push(value),
+                EditionValues::Identifier(value) => identifiers.push(value),
+                EditionValues::Issued(value) => issued.push(value),
+                EditionValues::Editeur(value) => editeurs.push(value),
+                _ => {}
+            }
+        }
+
+        self.cardinality(format!("{}/medium", path), media.len(), 1, Some(1));
+        each(path, "medium", &media, |path, medium| {
+            self.required(path.clone(), medium.text());
+            self.xsi_type(format!("{}/@type", path), medium.r#type(), "dcterms:IMT");
+        });
+
+        self.cardinality(format!("{}/extent", path), extents.len(), 0, Some(1));
+
+        self.cardinality(format!("{}/identifier", path), identifiers.len(), 1, None);
+        each(path, "identifier", &identifiers, |path, identifier| {
+            self.required(path.clone(), identifier.text());
+            self.xsi_type(
+                format!("{}/@type", path),
+                identifier.r#type(),
+                "dcterms:URI",
+            );
+        });
+
+        self.cardinality(format!("{}/issued", path), issued.len(), 0, Some(1));
+        each(path, "issued", &issued, |path, issued| {
+            self.required(path.clone(), issued.text());
+            self.xsi_type(format!("{}/@type", path), issued.r#type(), "dcterms:W3CDTF");
+        });
+
+        each(path, "editeur", &editeurs, |path, editeur| {
+            self.required(format!("{}/nom", path), &editeur.nom().0);
+            self.autorites_externes(
+                &path,
+                editeur.autorite_externe().as_deref().unwrap_or_default(),
+            );
+        });
+    }
+
+    fn meta_fichier(&mut self, path: &str, meta: &MetaFichier) {
+        let taille = meta.taille().0.trim();
+        let taille_path = format!("{}/taille", path);
+
+        if taille.is_empty() {
+            self.report.error(taille_path, "mandatory value is empty");
+        } else if taille.parse::<u64>().is_err() {
+            self.report.warning(
+                taille_path,
+                format!("expected a size in bytes, found \"{}\"", taille),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_tef;
+
+    use crate::fixtures::{these_with_editeur, THESE};
+
+    fn errors(report: &ValidationReport) -> Vec<(&str, &str)> {
+        report
+            .errors()
+            .map(|error| (error.path().as_str(), error.message().as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn cardinalities() {
+        let mut mets = parse_tef(THESE).unwrap();
+        assert!(validate(&mets).is_empty());
+
+        mets.thesis_record_mut()
+            .unwrap()
+            .title_mut()
+            .set_lang(String::new());
+        let admin = mets.thesis_admin_mut().unwrap();
+        admin.date_accepted_mut().set_text(" ".to_string());
+        admin.directeur_these_mut().clear();
+        admin.thesis_degree_mut().grantor_mut().clear();
+        admin.oai_set_spec_mut().clear();
+
+        let report = validate(&mets);
+        let errors = errors(&report);
+        let admin = "mets/amdSec/techMD[1]/mdWrap/xmlData/thesisAdmin";
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    "mets/dmdSec[1]/mdWrap/xmlData/thesisRecord/title/@lang",
+                    "mandatory value is empty"
+                ),
+                (
+                    &format!("{admin}/dateAccepted") as &str,
+                    "mandatory value is empty"
+                ),
+                (
+                    &format!("{admin}/thesis.degree/thesis.degree.grantor"),
+                    "mandatory element is missing"
+                ),
+                (
+                    &format!("{admin}/directeurThese"),
+                    "mandatory element is missing"
+                ),
+                (
+                    &format!("{admin}/oaiSetSpec"),
+                    "mandatory element is missing"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn sujet_rameau() {
+        let mut mets = parse_tef(THESE).unwrap();
+        mets.thesis_record_mut()
+            .unwrap()
+            .sujet_rameau_mut()
+            .as_mut()
+            .unwrap()
+            .values_mut()
+            .push(Some(SujetRameauValues::VedetteRameauNomGeographique(
+                VedetteRameauNomGeographique::default(),
+            )));
+
+        assert_eq!(
+            errors(&validate(&mets)),
+            [(
+                "mets/dmdSec[1]/mdWrap/xmlData/thesisRecord/sujetRameau/vedetteRameauNomGeographique/elementdEntree",
+                "mandatory value is empty"
+            )]
+        );
+    }
+
+    #[test]
+    fn editeur() {
+        let input = these_with_editeur(
+            r#"<tef:nom> </tef:nom>
+                <tef:place>Lyon</tef:place>
+                <tef:autoriteExterne autoriteSource="idref">026403448</tef:autoriteExterne>"#,
+        );
+        let report = validate(&parse_tef(&input).unwrap());
+        let paths: Vec<&str> = report.errors().map(|error| error.path().as_str()).collect();
+
+        assert!(paths
+            .iter()
+            .any(|path| path.ends_with("edition/editeur/nom")));
+        assert!(paths
+            .iter()
+            .any(|path| path.ends_with("edition/editeur/autoriteExterne")));
+    }
+}
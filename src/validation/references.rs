@@ -0,0 +1,395 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::mets::*;
+
+use super::{child, ValidationReport};
+
+/// Element holding an `ID` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    DmdSec,
+    TechMD,
+    RightsMD,
+    File,
+    FileGrp,
+    Div,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::DmdSec => write!(f, "mets:dmdSec"),
+            Kind::TechMD => write!(f, "mets:techMD"),
+            Kind::RightsMD => write!(f, "mets:rightsMD"),
+            Kind::File => write!(f, "mets:file"),
+            Kind::FileGrp => write!(f, "mets:fileGrp"),
+            Kind::Div => write!(f, "mets:div"),
+        }
+    }
+}
+
+/// Identifier declared by an `ID` attribute
+struct Declaration<'a> {
+    id: &'a str,
+    path: String,
+    kind: Kind,
+}
+
+/// Identifiers of an `IDREF` or `IDREFS` attribute
+struct Reference<'a> {
+    path: String,
+    ids: Vec<&'a str>,
+    expected: &'static [Kind],
+}
+
+const DMDID: &[Kind] = &[Kind::DmdSec];
+const ADMID: &[Kind] = &[Kind::TechMD, Kind::RightsMD];
+const FILEID: &[Kind] = &[Kind::File];
+
+/// Check the references between the sections of a record.
+///
+/// Every `ID` of the `mets:dmdSec`, `mets:techMD`, `mets:rightsMD`, `mets:file`, `mets:fileGrp` and `mets:div`
+/// elements is indexed. Duplicate identifiers and `DMDID`, `ADMID` or `FILEID` references to a missing (or wrong)
+/// element are errors. Metadata sections and files that nothing points to are reported as warnings.
+pub fn check_references(mets: &Mets) -> ValidationReport {
+    let mut collector = Collector::default();
+    collector.mets(mets);
+
+    let mut report = ValidationReport::default();
+    let mut index: HashMap<&str, &Declaration> = HashMap::new();
+
+    for declaration in &collector.declarations {
+        match index.get(declaration.id) {
+            Some(first) => report.error(
+                format!("{}/@ID", declaration.path),
+                format!(
+                    "duplicate ID \"{}\", already used by {}",
+                    declaration.id, first.path
+                ),
+            ),
+            None => {
+                index.insert(declaration.id, declaration);
+            }
+        }
+    }
+
+    let mut referenced: HashSet<&str> = HashSet::new();
+
+    for reference in &collector.references {
+        for id in &reference.ids {
+            match index.get(id) {
+                None => report.error(
+                    reference.path.clone(),
+                    format!("dangling reference \"{}\"", id),
+                ),
+                Some(target) if !reference.expected.contains(&target.kind) => report.error(
+                    reference.path.clone(),
+                    format!(
+                        "reference \"{}\" points to a {}, expected {}",
+                        id,
+                        target.kind,
+                        expected(reference.expected)
+                    ),
+                ),
+                Some(_) => {
+                    referenced.insert(id);
+                }
+            }
+        }
+    }
+
+    for declaration in &collector.declarations {
+        let orphan = matches!(
+            declaration.kind,
+            Kind::DmdSec | Kind::TechMD | Kind::RightsMD | Kind::File
+        ) && !referenced.contains(declaration.id);
+
+        if orphan {
+            report.warning(
+                declaration.path.clone(),
+                format!(
+                    "{} \"{}\" is not referenced",
+                    declaration.kind, declaration.id
+                ),
+            );
+        }
+    }
+
+    report
+}
+
+fn expected(kinds: &[Kind]) -> String {
+    kinds
+        .iter()
+        .map(Kind::to_string)
+        .collect::<Vec<String>>()
+        .join(" or ")
+}
+
+#[derive(Default)]
+struct Collector<'a> {
+    declarations: Vec<Declaration<'a>>,
+    references: Vec<Reference<'a>>,
+}
+
+impl<'a> Collector<'a> {
+    fn declare(&mut self, id: &'a str, path: String, kind: Kind) {
+        self.declarations.push(Declaration { id, path, kind });
+    }
+
+    fn refer(&mut self, path: String, ids: &'a str, expected: &'static [Kind]) {
+        self.references.push(Reference {
+            path,
+            ids: ids.split_whitespace().collect(),
+            expected,
+        });
+    }
+
+    fn mets(&mut self, mets: &'a Mets) {
+        let root = "mets";
+        let count = |f: fn(&MetsValues) -> bool| mets.values().iter().filter(|v| f(v)).count();
+        let counts = [
+            count(|value| matches!(value, MetsValues::DmdSec(_))),
+            count(|value| matches!(value, MetsValues::AmdSec(_))),
+            count(|value| matches!(value, MetsValues::FileSec(_))),
+            count(|value| matches!(value, MetsValues::StructMap(_))),
+        ];
+        let mut indexes = [0; 4];
+
+        for value in mets.values() {
+            match value {
+                MetsValues::DmdSec(dmd_sec) => {
+                    let path = child(root, "dmdSec", indexes[0], counts[0]);
+                    indexes[0] += 1;
+                    self.declare(dmd_sec.id(), path, Kind::DmdSec);
+                }
+                MetsValues::AmdSec(amd_sec) => {
+                    let path = child(root, "amdSec", indexes[1], counts[1]);
+                    indexes[1] += 1;
+                    self.amd_sec(&path, amd_sec);
+                }
+                MetsValues::FileSec(file_sec) => {
+                    let path = child(root, "fileSec", indexes[2], counts[2]);
+                    indexes[2] += 1;
+                    self.file_grps(&path, file_sec.value().iter().collect());
+                }
+                MetsValues::StructMap(struct_map) => {
+                    let path = child(root, "structMap", indexes[3], counts[3]);
+                    indexes[3] += 1;
+                    self.divs(&path, struct_map.value().iter().collect());
+                }
+                MetsValues::MetsHdr(_) => {}
+            }
+        }
+    }
+
+    fn amd_sec(&mut self, path: &str, amd_sec: &'a AmdSec) {
+        let count = |f: fn(&AmdSecValues) -> bool| amd_sec.values().iter().filter(|v| f(v)).count();
+        let tech_mds = count(|value| matches!(value, AmdSecValues::TechMD(_)));
+        let rights_mds = count(|value| matches!(value, AmdSecValues::RightsMD(_)));
+        let (mut tech_index, mut rights_index) = (0, 0);
+
+        for value in amd_sec.values() {
+            match value {
+                AmdSecValues::TechMD(tech_md) => {
+                    let path = child(path, "techMD", tech_index, tech_mds);
+                    tech_index += 1;
+                    self.declare(tech_md.id(), path, Kind::TechMD);
+                }
+                AmdSecValues::RightsMD(rights_md) => {
+                    let path = child(path, "rightsMD", rights_index, rights_mds);
+                    rights_index += 1;
+                    if let Some(id) = rights_md.id() {
+                        self.declare(id, path, Kind::RightsMD);
+                    }
+                }
+                AmdSecValues::Other(_) => {}
+            }
+        }
+    }
+
+    fn file_grps(&mut self, path: &str, file_grps: Vec<&'a FileGrp>) {
+        for (index, file_grp) in file_grps.iter().enumerate() {
+            let path = child(path, "fileGrp", index, file_grps.len());
+
+            if let Some(id) = file_grp.id() {
+                self.declare(id, path.clone(), Kind::FileGrp);
+            }
+
+            let files: Vec<&File> = file_grp
+                .values()
+                .iter()
+                .filter_map(|value| match value {
+                    FileGrpValues::File(file) => Some(file),
+                    FileGrpValues::FileGrp(_) => None,
+                })
+                .collect();
+
+            for (index, file) in files.iter().enumerate() {
+                let path = child(&path, "file", index, files.len());
+                self.declare(file.id(), path.clone(), Kind::File);
+                self.refer(format!("{}/@ADMID", path), file.admid(), ADMID);
+            }
+
+            self.file_grps(
+                &path,
+                file_grp
+                    .values()
+                    .iter()
+                    .filter_map(|value| match value {
+                        FileGrpValues::FileGrp(file_grp) => Some(file_grp),
+                        FileGrpValues::File(_) => None,
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    fn divs(&mut self, path: &str, divs: Vec<&'a Div>) {
+        for (index, div) in divs.iter().enumerate() {
+            let path = child(path, "div", index, divs.len());
+
+            if let Some(id) = div.id() {
+                self.declare(id, path.clone(), Kind::Div);
+            }
+            if let Some(dmdid) = div.dmdid() {
+                self.refer(format!("{}/@DMDID", path), dmdid, DMDID);
+            }
+            if let Some(admid) = div.amdid() {
+                self.refer(format!("{}/@ADMID", path), admid, ADMID);
+            }
+
+            let fptrs: Vec<&Fptr> = div
+                .values()
+                .iter()
+                .filter_map(|value| match value {
+                    DivValues::Fptr(fptr) => Some(fptr),
+                    DivValues::Div(_) => None,
+                })
+                .collect();
+
+            for (index, fptr) in fptrs.iter().enumerate() {
+                if let Some(fileid) = fptr.fileid() {
+                    let path = child(&path, "fptr", index, fptrs.len());
+                    self.refer(format!("{}/@FILEID", path), fileid, FILEID);
+                }
+            }
+
+            self.divs(
+                &path,
+                div.values()
+                    .iter()
+                    .filter_map(|value| match value {
+                        DivValues::Div(div) => Some(div.as_ref()),
+                        DivValues::Fptr(_) => None,
+                    })
+                    .collect(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{these_with, THESE};
+    use crate::parse_tef;
+    use crate::validation::Severity;
+
+    fn check(replacements: &[(&str, &str)]) -> Vec<(Severity, String, String)> {
+        check_references(&parse_tef(&these_with(replacements)).unwrap())
+            .into_iter()
+            .map(|violation| {
+                (
+                    *violation.severity(),
+                    violation.path().clone(),
+                    violation.message().clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn violation(severity: Severity, path: &str, message: &str) -> (Severity, String, String) {
+        (severity, path.to_string(), message.to_string())
+    }
+
+    #[test]
+    fn fixture() {
+        assert!(check_references(&parse_tef(THESE).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn duplicate_id() {
+        assert_eq!(
+            check(&[(r#"<mets:div ID="edition_1""#, r#"<mets:div ID="file_1""#)]),
+            [violation(
+                Severity::Error,
+                "mets/structMap/div/div/div/@ID",
+                r#"duplicate ID "file_1", already used by mets/fileSec/fileGrp/file"#
+            )]
+        );
+    }
+
+    #[test]
+    fn dangling_reference() {
+        assert_eq!(
+            check(&[(r#"DMDID="desc_edition""#, r#"DMDID="desc_edition_2""#)]),
+            [
+                violation(
+                    Severity::Error,
+                    "mets/structMap/div/div/div/@DMDID",
+                    r#"dangling reference "desc_edition_2""#
+                ),
+                violation(
+                    Severity::Warning,
+                    "mets/dmdSec[2]",
+                    r#"mets:dmdSec "desc_edition" is not referenced"#
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrong_kind() {
+        assert_eq!(
+            check(&[(
+                r#"ADMID="admin_these droits_these""#,
+                r#"ADMID="admin_these droits_these desc_these""#
+            )]),
+            [violation(
+                Severity::Error,
+                "mets/structMap/div/@ADMID",
+                r#"reference "desc_these" points to a mets:dmdSec, expected mets:techMD or mets:rightsMD"#
+            )]
+        );
+    }
+
+    #[test]
+    fn orphans() {
+        // the techMD of the file is still referenced by the unreferenced file
+        let violations = check(&[
+            (
+                r#"ADMID="admin_these droits_these""#,
+                r#"ADMID="droits_these""#,
+            ),
+            (r#"<mets:fptr FILEID="file_1"/>"#, ""),
+        ]);
+
+        assert_eq!(
+            violations,
+            [
+                violation(
+                    Severity::Warning,
+                    "mets/amdSec/techMD[1]",
+                    r#"mets:techMD "admin_these" is not referenced"#
+                ),
+                violation(
+                    Severity::Warning,
+                    "mets/fileSec/fileGrp/file",
+                    r#"mets:file "file_1" is not referenced"#
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Exit codes, inputs and outputs of the `tef` command
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+const THESE: &str = include_str!("data/these.xml");
+
+/// Run `tef` with the given arguments, `stdin` written to its standard input
+fn tef(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tef"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Empty directory of a test, under the target directory
+fn directory(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn valid() {
+    let output = tef(&["validate", "--quiet"], THESE);
+
+    assert_eq!(output.status.code(), Some(0), "{}", stdout(&output));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn invalid() {
+    let input = THESE.replace(r#"<dc:title xml:lang="fr">"#, r#"<dc:title xml:lang="">"#);
+    let output = tef(&["validate", "--quiet"], &input);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout(&output).contains("thesisRecord/title/@lang: mandatory value is empty"),
+        "{}",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn unreadable() {
+    let dir = directory("unreadable");
+    let missing = dir.join("absente.xml");
+    let output = tef(&["validate", missing.to_str().unwrap()], "");
+
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = tef(&["validate"], "<mets:mets");
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("-: "));
+}
+
+#[test]
+fn stdin_read_once() {
+    let output = tef(&["extract", "nnt", "-", "-"], THESE);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "2019LYSE1234\n");
+}
+
+#[test]
+fn convert_keeps_the_tree() {
+    let dir = directory("convert");
+    let inputs = dir.join("inputs");
+    fs::create_dir_all(inputs.join("2019/lyon")).unwrap();
+    fs::write(inputs.join("these.xml"), THESE).unwrap();
+    fs::write(inputs.join("2019/lyon/these.xml"), THESE).unwrap();
+    fs::write(inputs.join("2019/notes.txt"), "pas une notice").unwrap();
+
+    let output = dir.join("output");
+    let result = tef(
+        &[
+            "convert",
+            "--to",
+            "ris",
+            "--output",
+            output.to_str().unwrap(),
+            inputs.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert_eq!(result.status.code(), Some(0));
+
+    let mut written: Vec<String> = vec![];
+    let mut dirs = vec![output.clone()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            match path.is_dir() {
+                true => dirs.push(path),
+                false => written.push(
+                    path.strip_prefix(&output)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/"),
+                ),
+            }
+        }
+    }
+    written.sort();
+    assert_eq!(written, ["2019/lyon/these.ris", "these.ris"]);
+    assert!(fs::read_to_string(output.join("these.ris"))
+        .unwrap()
+        .starts_with("TY  - THES"));
+}